@@ -0,0 +1,64 @@
+//! Generates the tables of embedded inputs and examples for the `embed`
+//! feature. Only files that exist at build time are embedded, so builds
+//! without any inputs still compile.
+
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    let embed = env::var_os("CARGO_FEATURE_EMBED").is_some();
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let root = Path::new(&root);
+
+    let mut inputs = String::new();
+    let mut examples = String::new();
+
+    if embed {
+        // Cargo scans watched directories for changes, but reruns the script
+        // on every build if a watched path is missing
+        watch(root, "data/inputs");
+        watch(root, "data/examples");
+
+        for day in 1..=25 {
+            let input = root.join(format!("data/inputs/{:02}.txt", day));
+            if input.exists() {
+                writeln!(inputs, "    ({}, include_str!({:?})),", day, input).unwrap();
+            }
+
+            for part in 1..=2 {
+                let example = root.join(format!("data/examples/{:02}/part-{}.txt", day, part));
+                if example.exists() {
+                    writeln!(
+                        examples,
+                        "    ({}, {}, include_str!({:?})),",
+                        day, part, example
+                    )
+                    .unwrap();
+                }
+            }
+        }
+    }
+
+    let generated = format!(
+        "pub static INPUTS: &[(u32, &str)] = &[\n{}];\n\n\
+         pub static EXAMPLES: &[(u32, u8, &str)] = &[\n{}];\n",
+        inputs, examples
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("embedded.rs"), generated).unwrap();
+}
+
+/// Asks cargo to rerun the build script when a path changes. If the path does
+/// not exist yet, its closest existing parent is watched instead, which
+/// changes when the path is created.
+fn watch(root: &Path, path: &str) {
+    let watched = Path::new(path)
+        .ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && root.join(ancestor).exists());
+
+    if let Some(watched) = watched {
+        println!("cargo:rerun-if-changed={}", watched.display());
+    }
+}
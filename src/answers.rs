@@ -0,0 +1,65 @@
+use std::{collections::BTreeMap, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Part;
+
+/// Where accepted answers are stored. Unlike the inputs, these can be committed.
+pub fn answers_path() -> PathBuf {
+    PathBuf::from("data/answers.json")
+}
+
+/// Answers that were accepted on adventofcode.com, keyed by day and part
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<u32, BTreeMap<u8, String>>);
+
+impl Answers {
+    /// Loads the answers, returning an empty set if none have been recorded
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(answers_path()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("answers are always serializable");
+        fs::write(answers_path(), json + "\n")
+    }
+
+    pub fn get(&self, day: u32, part: Part) -> Option<&str> {
+        self.0.get(&day)?.get(&(part as u8)).map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u32, part: Part, answer: &str) {
+        self.0
+            .entry(day)
+            .or_default()
+            .insert(part as u8, answer.trim().to_string());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        assert_eq!(answers.get(1, Part::One), None);
+
+        answers.record(1, Part::One, "54667\n");
+        answers.record(1, Part::Two, "54203");
+        answers.record(10, Part::One, "6867");
+
+        assert_eq!(answers.get(1, Part::One), Some("54667"));
+        assert_eq!(answers.get(10, Part::Two), None);
+
+        let json = serde_json::to_string(&answers).unwrap();
+        assert_eq!(json, r#"{"1":{"1":"54667","2":"54203"},"10":{"1":"6867"}}"#);
+        assert_eq!(serde_json::from_str::<Answers>(&json).unwrap(), answers);
+    }
+}
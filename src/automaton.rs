@@ -0,0 +1,184 @@
+/// An Aho–Corasick automaton that finds every occurrence of a set of byte
+/// patterns in a single pass, including occurrences that overlap (such as
+/// "one" and "eight" in "oneight"). Each pattern carries a value that is
+/// returned with its matches.
+///
+/// The transitions are stored as a dense table with one row of 256 entries
+/// per trie node, so each byte of the haystack costs a single lookup.
+#[derive(Debug, Clone)]
+pub struct AhoCorasick<T> {
+    transitions: Vec<[u32; 256]>,
+    /// The patterns ending at each state, longest first
+    outputs: Vec<Vec<usize>>,
+    /// The length and value of each pattern
+    patterns: Vec<(usize, T)>,
+}
+
+/// An occurrence of a pattern in a haystack
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match<'a, T> {
+    /// Byte offset of the first byte of the occurrence
+    pub start: usize,
+    /// Byte offset just past the last byte of the occurrence
+    pub end: usize,
+    pub value: &'a T,
+}
+
+/// Where the automaton is after reading some bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct State(usize);
+
+const ROOT: usize = 0;
+
+impl<T> AhoCorasick<T> {
+    /// Builds the automaton for the given patterns. Panics if a pattern is empty.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = (P, T)>) -> Self {
+        // Build the trie, where a zero entry means there is no child yet
+        // (the root is never a child, so zero is free to use)
+        let mut transitions = vec![[0u32; 256]];
+        let mut outputs = vec![Vec::new()];
+        let mut values = Vec::new();
+
+        for (index, (pattern, value)) in patterns.into_iter().enumerate() {
+            let pattern = pattern.as_ref();
+            assert!(!pattern.is_empty(), "Patterns must not be empty");
+
+            let mut state = ROOT;
+            for &byte in pattern {
+                state = match transitions[state][byte as usize] {
+                    0 => {
+                        transitions.push([0; 256]);
+                        outputs.push(Vec::new());
+
+                        let child = transitions.len() - 1;
+                        transitions[state][byte as usize] = child as u32;
+                        child
+                    }
+                    child => child as usize,
+                };
+            }
+
+            outputs[state].push(index);
+            values.push((pattern.len(), value));
+        }
+
+        // Breadth first, point each state at the longest proper suffix that is
+        // also in the trie and fill in the missing transitions from there.
+        // Missing transitions from the root stay at zero, i.e. the root.
+        let mut failures = vec![ROOT; transitions.len()];
+        let mut queue: std::collections::VecDeque<_> = transitions[ROOT]
+            .iter()
+            .filter(|&&child| child != 0)
+            .map(|&child| child as usize)
+            .collect();
+
+        while let Some(state) = queue.pop_front() {
+            let row = transitions[state];
+
+            for (byte, &child) in row.iter().enumerate() {
+                let child = child as usize;
+                let fallback = transitions[failures[state]][byte];
+
+                if child == 0 {
+                    transitions[state][byte] = fallback;
+                    continue;
+                }
+
+                failures[child] = fallback as usize;
+
+                // The fallback is shallower, so its outputs are already complete
+                let inherited = outputs[fallback as usize].clone();
+                outputs[child].extend(inherited);
+
+                queue.push_back(child);
+            }
+        }
+
+        Self {
+            transitions,
+            outputs,
+            patterns: values,
+        }
+    }
+
+    /// The state before any bytes have been read
+    pub fn start(&self) -> State {
+        State(ROOT)
+    }
+
+    /// Reads one byte. Scanning byte by byte lets callers stop early or feed
+    /// the bytes in any order, such as backwards with reversed patterns.
+    pub fn next_state(&self, state: State, byte: u8) -> State {
+        State(self.transitions[state.0][byte as usize] as usize)
+    }
+
+    /// The length and value of each pattern ending at a state, longest first
+    pub fn outputs(&self, state: State) -> impl Iterator<Item = (usize, &T)> + '_ {
+        self.outputs[state.0].iter().map(|&index| {
+            let (length, value) = &self.patterns[index];
+            (*length, value)
+        })
+    }
+
+    /// Finds every occurrence of every pattern, in order of where they end.
+    /// Occurrences ending at the same byte are returned longest first.
+    pub fn find_overlapping<'a>(
+        &'a self,
+        haystack: &'a [u8],
+    ) -> impl Iterator<Item = Match<'a, T>> + 'a {
+        let mut state = self.start();
+
+        haystack.iter().enumerate().flat_map(move |(i, &byte)| {
+            state = self.next_state(state, byte);
+
+            self.outputs(state).map(move |(length, value)| Match {
+                start: i + 1 - length,
+                end: i + 1,
+                value,
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matches<'a>(automaton: &'a AhoCorasick<u32>, haystack: &'a str) -> Vec<(usize, u32)> {
+        automaton
+            .find_overlapping(haystack.as_bytes())
+            .map(|m| (m.start, *m.value))
+            .collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let automaton = AhoCorasick::new([("one", 1), ("eight", 8), ("two", 2), ("1", 1)]);
+
+        assert_eq!(matches(&automaton, "oneight"), [(0, 1), (2, 8)]);
+        assert_eq!(
+            matches(&automaton, "eightwone1"),
+            [(0, 8), (4, 2), (6, 1), (9, 1)]
+        );
+        assert_eq!(matches(&automaton, "xyz"), []);
+        assert_eq!(matches(&automaton, ""), []);
+    }
+
+    #[test]
+    fn reports_suffixes_and_prefixes() {
+        let automaton = AhoCorasick::new([("he", 0), ("she", 1), ("his", 2), ("hers", 3)]);
+        let found: Vec<_> = automaton
+            .find_overlapping(b"ushers")
+            .map(|m| (m.start, m.end, *m.value))
+            .collect();
+
+        assert_eq!(found, [(1, 4, 1), (2, 4, 0), (2, 6, 3)]);
+    }
+
+    #[test]
+    fn matches_multibyte_patterns() {
+        let automaton = AhoCorasick::new([("drei", 3), ("trois", 3), ("три", 3)]);
+
+        assert_eq!(matches(&automaton, "xтриdrei"), [(1, 3), (7, 3)]);
+    }
+}
@@ -0,0 +1,350 @@
+use std::{
+    fmt, fs, io,
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+
+use reqwest::{blocking, header};
+
+use crate::{
+    config::{DownloaderConfig, SessionToken},
+    manifest, time,
+};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: i64 = 2023;
+
+const REPOSITORY: &str = "github.com/wowkster/advent-of-code-2023";
+
+/// A response body, and whether it came from the local cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fetched {
+    pub body: String,
+    pub cached: bool,
+}
+
+/// How long a cached response may be used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CachePolicy {
+    Never,
+    Forever,
+    MaxAge(Duration),
+}
+
+/// The automation guidelines ask for leaderboards to be requested at most
+/// once every 15 minutes
+pub const LEADERBOARD_MAX_AGE: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// The puzzle for the day has not been released yet
+    Locked {
+        day: u32,
+        unlocks_at: i64,
+    },
+    /// The server did not accept the session token
+    SessionExpired,
+    Http(reqwest::Error),
+    Status {
+        url: String,
+        status: u16,
+    },
+    Cache {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The response does not look like what was requested (e.g. an error
+    /// page), so it was not cached
+    Suspicious {
+        url: String,
+        reason: String,
+    },
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Locked { day, unlocks_at } => write!(
+                f,
+                "day {} unlocks at {}",
+                day,
+                time::format_utc(*unlocks_at)
+            ),
+            ClientError::SessionExpired => write!(
+                f,
+                "the session token was rejected. Session tokens expire after about a month, \
+                 so log in to {} again and copy the new `session` cookie",
+                BASE_URL
+            ),
+            ClientError::Http(e) => write!(f, "request failed: {}", e),
+            ClientError::Status { url, status } => {
+                write!(f, "{} responded with status {}", url, status)
+            }
+            ClientError::Cache { path, source } => {
+                write!(f, "failed to write {}: {}", path.display(), source)
+            }
+            ClientError::Suspicious { url, reason } => write!(f, "{}: {}", url, reason),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<reqwest::Error> for ClientError {
+    fn from(e: reqwest::Error) -> Self {
+        ClientError::Http(e)
+    }
+}
+
+/// A client for the Advent of Code website that follows the automation
+/// guidelines: it identifies itself, throttles requests and caches responses
+pub struct Client {
+    http: blocking::Client,
+    session_token: SessionToken,
+    /// Whether the token has been checked with [`Client::whoami`]
+    session_checked: bool,
+    throttle: Duration,
+    cache_dir: PathBuf,
+    /// Ignore cached responses (they are still updated)
+    refresh: bool,
+    last_request: Option<Instant>,
+}
+
+impl Client {
+    pub fn new(
+        session_token: SessionToken,
+        config: &DownloaderConfig,
+    ) -> Result<Self, ClientError> {
+        let http = blocking::Client::builder()
+            .user_agent(user_agent(config.contact.as_deref()))
+            .build()?;
+
+        Ok(Self {
+            http,
+            session_token,
+            session_checked: false,
+            throttle: config.throttle(),
+            cache_dir: config.cache_dir.clone(),
+            refresh: false,
+            last_request: None,
+        })
+    }
+
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    pub fn get_input(&mut self, day: u32) -> Result<Fetched, ClientError> {
+        let unlocks_at = unlock_time(day);
+        if time::now() < unlocks_at {
+            return Err(ClientError::Locked { day, unlocks_at });
+        }
+
+        let path = format!("/{}/day/{}/input", YEAR, day);
+
+        // Check the token before the first real download, so an expired token
+        // is reported clearly instead of as a failed request
+        if !self.session_checked && self.cached(&path, CachePolicy::Forever).is_none() {
+            self.whoami()?;
+        }
+
+        self.get_checked(&path, CachePolicy::Forever, manifest::suspicious_content)
+            .map_err(|e| match e {
+                ClientError::Status { status: 400, .. } => ClientError::SessionExpired,
+                e => e,
+            })
+    }
+
+    /// Returns the name of the user the session token belongs to, or
+    /// [`ClientError::SessionExpired`] if the server does not recognise it
+    pub fn whoami(&mut self) -> Result<String, ClientError> {
+        let page = self.get(&format!("/{}", YEAR), CachePolicy::Never)?;
+        let user = parse_user(&page.body).ok_or(ClientError::SessionExpired)?;
+
+        self.session_checked = true;
+        Ok(user)
+    }
+
+    /// Returns the JSON export of a private leaderboard
+    pub fn get_leaderboard(&mut self, id: u64) -> Result<Fetched, ClientError> {
+        self.get(
+            &format!("/{}/leaderboard/private/view/{}.json", YEAR, id),
+            CachePolicy::MaxAge(LEADERBOARD_MAX_AGE),
+        )
+    }
+
+    fn cached(&self, path: &str, policy: CachePolicy) -> Option<String> {
+        let path = cache_path(&self.cache_dir, path);
+
+        let fresh = match policy {
+            _ if self.refresh => false,
+            CachePolicy::Never => false,
+            CachePolicy::Forever => true,
+            CachePolicy::MaxAge(max_age) => fs::metadata(&path)
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| modified.elapsed().ok())
+                .is_some_and(|age| age < max_age),
+        };
+
+        fresh.then(|| fs::read_to_string(path).ok()).flatten()
+    }
+
+    /// Requests a path on the website. Responses are served from the cache
+    /// when the policy allows it, and only successful responses are cached.
+    pub fn get(&mut self, path: &str, policy: CachePolicy) -> Result<Fetched, ClientError> {
+        self.get_checked(path, policy, |_| None)
+    }
+
+    /// Like [`Client::get`], but `check` can reject a body by describing
+    /// what is wrong with it. Rejected bodies are never cached, and rejected
+    /// cache entries are requested again.
+    fn get_checked(
+        &mut self,
+        path: &str,
+        policy: CachePolicy,
+        check: fn(&str) -> Option<String>,
+    ) -> Result<Fetched, ClientError> {
+        if let Some(body) = self.cached(path, policy) {
+            if check(&body).is_none() {
+                return Ok(Fetched { body, cached: true });
+            }
+        }
+
+        if let Some(last_request) = self.last_request {
+            std::thread::sleep(self.throttle.saturating_sub(last_request.elapsed()));
+        }
+        self.last_request = Some(Instant::now());
+
+        let url = format!("{}{}", BASE_URL, path);
+        let response = self
+            .http
+            .get(&url)
+            .header(
+                header::COOKIE,
+                format!("session={}", self.session_token.as_str()),
+            )
+            .send()?;
+
+        if !response.status().is_success() {
+            return Err(ClientError::Status {
+                url,
+                status: response.status().as_u16(),
+            });
+        }
+
+        let body = response.text()?;
+
+        if let Some(reason) = check(&body) {
+            return Err(ClientError::Suspicious { url, reason });
+        }
+
+        if policy != CachePolicy::Never {
+            let cache_path = cache_path(&self.cache_dir, path);
+            let write = |path: &PathBuf| {
+                fs::create_dir_all(path.parent().unwrap())?;
+                fs::write(path, &body)
+            };
+
+            write(&cache_path).map_err(|source| ClientError::Cache {
+                path: cache_path,
+                source,
+            })?;
+        }
+
+        Ok(Fetched {
+            body,
+            cached: false,
+        })
+    }
+}
+
+/// Identifies the tool and, if configured, who is running it
+pub fn user_agent(contact: Option<&str>) -> String {
+    let agent = format!(
+        "{}/{} (+{}",
+        env!("CARGO_PKG_NAME"),
+        env!("CARGO_PKG_VERSION"),
+        REPOSITORY
+    );
+
+    match contact.map(str::trim).filter(|c| !c.is_empty()) {
+        Some(contact) => format!("{}; {})", agent, contact),
+        None => agent + ")",
+    }
+}
+
+/// Extracts the user name from the header of a page, which is only present
+/// when logged in (e.g. `<div class="user">name <span class="star-count">`)
+pub fn parse_user(html: &str) -> Option<String> {
+    let (_, rest) = html.split_once(r#"<div class="user">"#)?;
+    let name = rest[..rest.find('<')?].trim();
+
+    (!name.is_empty()).then(|| name.to_string())
+}
+
+/// Puzzles unlock at midnight EST (05:00 UTC) on each day of December
+pub fn unlock_time(day: u32) -> i64 {
+    time::unix_from_utc(YEAR, 12, day, 5, 0, 0)
+}
+
+fn cache_path(cache_dir: &std::path::Path, path: &str) -> PathBuf {
+    path.split('/')
+        .filter(|s| !s.is_empty() && *s != "..")
+        .fold(cache_dir.to_path_buf(), |dir, segment| dir.join(segment))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_user_agent() {
+        assert_eq!(
+            user_agent(None),
+            "advent_of_code_2023/0.1.0 (+github.com/wowkster/advent-of-code-2023)"
+        );
+        assert_eq!(
+            user_agent(Some(" dev@example.com ")),
+            "advent_of_code_2023/0.1.0 (+github.com/wowkster/advent-of-code-2023; dev@example.com)"
+        );
+        assert_eq!(user_agent(Some("")), user_agent(None));
+    }
+
+    #[test]
+    fn parses_user() {
+        assert_eq!(
+            parse_user(
+                r#"<div><a href="/2023/settings">[Settings]</a></div><div class="user">wowkster <span class="star-count">16*</span></div>"#
+            ),
+            Some("wowkster".to_string())
+        );
+        assert_eq!(
+            parse_user(r#"<div class="user">(anonymous user #123456)</div>"#),
+            Some("(anonymous user #123456)".to_string())
+        );
+        assert_eq!(
+            parse_user(r#"<div><a href="/2023/auth/login">[Log In]</a></div>"#),
+            None
+        );
+    }
+
+    #[test]
+    fn unlock_times() {
+        assert_eq!(time::format_utc(unlock_time(1)), "2023-12-01 05:00:00 UTC");
+        assert_eq!(time::format_utc(unlock_time(25)), "2023-12-25 05:00:00 UTC");
+    }
+
+    #[test]
+    fn cache_paths() {
+        assert_eq!(
+            cache_path("data/cache".as_ref(), "/2023/day/1/input"),
+            PathBuf::from("data/cache/2023/day/1/input")
+        );
+        assert_eq!(
+            cache_path("cache".as_ref(), "/../../etc/passwd"),
+            PathBuf::from("cache/etc/passwd")
+        );
+    }
+}
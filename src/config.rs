@@ -0,0 +1,297 @@
+use std::{fmt, fs, io, path::PathBuf, time::Duration};
+
+use serde::{de, Deserialize, Deserializer};
+
+/// Environment variable that overrides the location of the config file
+pub const CONFIG_ENV_VAR: &str = "AOC_CONFIG";
+
+/// Environment variable that can hold the session token
+pub const SESSION_ENV_VAR: &str = "AOC_SESSION_TOKEN";
+
+/// File (relative to the repository root) that can hold the session token
+pub const SESSION_FILE: &str = ".aoc-session";
+
+/// Settings for the input downloader, read from the user's config file
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    /// The value of the `session` cookie from adventofcode.com
+    pub session_token: Option<String>,
+    pub downloader: DownloaderConfig,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DownloaderConfig {
+    /// Contact details (e.g. an email address) included in the User-Agent,
+    /// so the Advent of Code maintainers can reach you if something goes wrong
+    pub contact: Option<String>,
+    /// Minimum number of seconds between requests to the server
+    #[serde(deserialize_with = "deserialize_throttle")]
+    pub throttle_secs: f64,
+    /// Where server responses are cached. Inputs never change, so they are
+    /// only ever requested once.
+    pub cache_dir: PathBuf,
+}
+
+impl Default for DownloaderConfig {
+    fn default() -> Self {
+        Self {
+            contact: None,
+            throttle_secs: 5.0,
+            cache_dir: PathBuf::from("data/cache"),
+        }
+    }
+}
+
+impl DownloaderConfig {
+    pub fn throttle(&self) -> Duration {
+        Duration::from_secs_f64(self.throttle_secs)
+    }
+}
+
+/// Rejects throttles that cannot be turned into a `Duration` (negative, NaN,
+/// infinite or too large), so `throttle` never panics
+fn deserialize_throttle<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let secs = f64::deserialize(deserializer)?;
+
+    match Duration::try_from_secs_f64(secs) {
+        Ok(_) => Ok(secs),
+        Err(_) => Err(de::Error::custom(format!(
+            "throttle_secs must be a non-negative number of seconds, got {}",
+            secs
+        ))),
+    }
+}
+
+/// The `session` cookie used to authenticate with adventofcode.com
+#[derive(Clone, PartialEq, Eq)]
+pub struct SessionToken(String);
+
+impl fmt::Debug for SessionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the token itself
+        f.write_str("SessionToken(..)")
+    }
+}
+
+impl SessionToken {
+    /// Accepts the bare token or a `session=...` cookie pasted from the browser
+    pub fn parse(token: &str) -> Option<Self> {
+        let token = token.trim();
+        let token = token.strip_prefix("session=").unwrap_or(token);
+
+        (!token.is_empty() && token.chars().all(|c| c.is_ascii_hexdigit()))
+            .then(|| Self(token.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Where the session token was found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SessionSource {
+    Env,
+    ConfigFile(PathBuf),
+    SessionFile,
+}
+
+impl fmt::Display for SessionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SessionSource::Env => write!(f, "the {} environment variable", SESSION_ENV_VAR),
+            SessionSource::ConfigFile(path) => write!(f, "{}", path.display()),
+            SessionSource::SessionFile => write!(f, "{}", SESSION_FILE),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Parse {
+        path: PathBuf,
+        source: toml::de::Error,
+    },
+    /// No session token was found anywhere
+    MissingSession,
+    /// The session token is not a hex string
+    InvalidSession {
+        source: SessionSource,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            ConfigError::Parse { path, source } => {
+                write!(f, "Invalid config file {}: {}", path.display(), source)
+            }
+            ConfigError::MissingSession => write!(
+                f,
+                "No session token found. Set {}, add `session_token` to {} or write it to {}",
+                SESSION_ENV_VAR,
+                config_path().map_or("the config file".into(), |p| p.display().to_string()),
+                SESSION_FILE
+            ),
+            ConfigError::InvalidSession { source } => write!(
+                f,
+                "The session token in {} is not valid. It should be the hex value of the `session` cookie",
+                source
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// The location of the config file: `$AOC_CONFIG`, or
+/// `advent_of_code_2023/config.toml` in the user's config directory
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CONFIG_ENV_VAR) {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME")?).join(".config"),
+    };
+
+    Some(config_dir.join("advent_of_code_2023").join("config.toml"))
+}
+
+impl Config {
+    /// Loads the config file, using the defaults if it does not exist
+    pub fn load() -> Result<Self, ConfigError> {
+        let Some(path) = config_path() else {
+            return Ok(Self::default());
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(contents) => {
+                Self::parse(&contents).map_err(|source| ConfigError::Parse { path, source })
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(source) => Err(ConfigError::Io { path, source }),
+        }
+    }
+
+    pub fn parse(contents: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(contents)
+    }
+
+    /// Finds the session token, preferring the `AOC_SESSION_TOKEN`
+    /// environment variable, then the config file, then `.aoc-session`
+    pub fn session_token(&self) -> Result<(SessionToken, SessionSource), ConfigError> {
+        let from_env = std::env::var(SESSION_ENV_VAR).ok();
+        let from_file = std::fs::read_to_string(SESSION_FILE).ok();
+
+        self.find_session_token(from_env, from_file)
+    }
+
+    fn find_session_token(
+        &self,
+        from_env: Option<String>,
+        from_file: Option<String>,
+    ) -> Result<(SessionToken, SessionSource), ConfigError> {
+        let (token, source) = if let Some(token) = from_env {
+            (token, SessionSource::Env)
+        } else if let Some(token) = self.session_token.clone() {
+            (
+                token,
+                SessionSource::ConfigFile(config_path().unwrap_or_default()),
+            )
+        } else if let Some(token) = from_file {
+            (token, SessionSource::SessionFile)
+        } else {
+            return Err(ConfigError::MissingSession);
+        };
+
+        match SessionToken::parse(&token) {
+            Some(token) => Ok((token, source)),
+            None => Err(ConfigError::InvalidSession { source }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_config() {
+        assert_eq!(Config::parse("").unwrap(), Config::default());
+
+        let config = Config::parse(
+            r#"
+            [downloader]
+            contact = "dev@example.com"
+            throttle_secs = 1.5
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.downloader.contact.as_deref(),
+            Some("dev@example.com")
+        );
+        assert_eq!(config.downloader.throttle(), Duration::from_millis(1500));
+        assert_eq!(config.downloader.cache_dir, PathBuf::from("data/cache"));
+
+        assert!(Config::parse("[downloader]\nthrottle = 1").is_err());
+        assert!(Config::parse("[downloader]\nthrottle_secs = 2").is_ok());
+        assert!(Config::parse("[downloader]\nthrottle_secs = -1.0").is_err());
+        assert!(Config::parse("[downloader]\nthrottle_secs = inf").is_err());
+        assert!(Config::parse("[downloader]\nthrottle_secs = nan").is_err());
+        assert!(Config::parse("[downloader]\nthrottle_secs = 1e30").is_err());
+    }
+
+    #[test]
+    fn finds_session_token() {
+        let config = Config::parse(r#"session_token = "c0ffee""#).unwrap();
+        let found = |config: &Config, env: Option<&str>, file: Option<&str>| {
+            config
+                .find_session_token(env.map(String::from), file.map(String::from))
+                .map(|(token, source)| (token.as_str().to_string(), source))
+        };
+
+        let (token, source) = found(&config, Some("abc123"), Some("def456")).unwrap();
+        assert_eq!((token.as_str(), source), ("abc123", SessionSource::Env));
+
+        let (token, source) = found(&config, None, Some("def456")).unwrap();
+        assert_eq!(token, "c0ffee");
+        assert!(matches!(source, SessionSource::ConfigFile(_)));
+
+        let (token, source) = found(&Config::default(), None, Some("session=def456\n")).unwrap();
+        assert_eq!(
+            (token.as_str(), source),
+            ("def456", SessionSource::SessionFile)
+        );
+
+        assert!(matches!(
+            found(&Config::default(), None, None),
+            Err(ConfigError::MissingSession)
+        ));
+        assert!(matches!(
+            found(&config, Some("not a token"), None),
+            Err(ConfigError::InvalidSession {
+                source: SessionSource::Env
+            })
+        ));
+    }
+
+    #[test]
+    fn hides_session_token() {
+        let token = SessionToken::parse("abc123").unwrap();
+        assert_eq!(format!("{:?}", token), "SessionToken(..)");
+        assert_eq!(SessionToken::parse(""), None);
+    }
+}
@@ -0,0 +1,207 @@
+use std::{fmt, path::Path};
+
+use chacha20poly1305::{
+    aead::{Aead, AeadCore, KeyInit, OsRng, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+
+/// Environment variable that can hold the input key as hex
+pub const KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+
+/// File (relative to the repository root) that holds the input key as hex
+pub const KEY_FILE: &str = ".aoc-input-key";
+
+/// Marks the start of an encrypted input file and its format version
+const MAGIC: &[u8; 4] = b"AOC1";
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptoError {
+    /// The key is not 64 hex characters
+    InvalidKey,
+    /// The file does not start with the expected header
+    InvalidFormat,
+    /// The file was encrypted with a different key, or has been modified
+    DecryptionFailed,
+    /// The decrypted contents are not valid UTF-8
+    InvalidUtf8,
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::InvalidKey => write!(f, "the input key must be 64 hex characters"),
+            CryptoError::InvalidFormat => write!(f, "not an encrypted input file"),
+            CryptoError::DecryptionFailed => write!(
+                f,
+                "decryption failed (wrong key, or the file has been modified)"
+            ),
+            CryptoError::InvalidUtf8 => write!(f, "decrypted input is not valid UTF-8"),
+        }
+    }
+}
+
+impl std::error::Error for CryptoError {}
+
+/// A locally held key used to encrypt puzzle inputs so they can be committed
+#[derive(Clone, PartialEq, Eq)]
+pub struct InputKey(Key);
+
+impl fmt::Debug for InputKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Never print the key itself
+        f.write_str("InputKey(..)")
+    }
+}
+
+impl InputKey {
+    pub fn generate() -> Self {
+        Self(ChaCha20Poly1305::generate_key(&mut OsRng))
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, CryptoError> {
+        let hex = hex.trim();
+
+        if hex.len() != 64 || !hex.is_ascii() {
+            return Err(CryptoError::InvalidKey);
+        }
+
+        let mut key = Key::default();
+        for (i, byte) in key.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16)
+                .map_err(|_| CryptoError::InvalidKey)?;
+        }
+
+        Ok(Self(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        self.0.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Loads the key from the `AOC_INPUT_KEY` environment variable or, failing
+    /// that, from the `.aoc-input-key` file. Returns `Ok(None)` if neither is set.
+    pub fn load() -> Result<Option<Self>, CryptoError> {
+        if let Ok(hex) = std::env::var(KEY_ENV_VAR) {
+            return Self::from_hex(&hex).map(Some);
+        }
+
+        match std::fs::read_to_string(Path::new(KEY_FILE)) {
+            Ok(hex) => Self::from_hex(&hex).map(Some),
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Encrypts the input for a day. The day is authenticated along with the
+    /// contents so that encrypted files cannot be swapped between days.
+    pub fn encrypt(&self, day: u32, plaintext: &str) -> Vec<u8> {
+        let cipher = ChaCha20Poly1305::new(&self.0);
+        let nonce = ChaCha20Poly1305::generate_nonce(&mut OsRng);
+        let aad = associated_data(day);
+
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: aad.as_bytes(),
+                },
+            )
+            .expect("encryption cannot fail for in-memory buffers");
+
+        let mut output = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+        output.extend_from_slice(MAGIC);
+        output.extend_from_slice(&nonce);
+        output.extend_from_slice(&ciphertext);
+        output
+    }
+
+    pub fn decrypt(&self, day: u32, data: &[u8]) -> Result<String, CryptoError> {
+        let data = data
+            .strip_prefix(MAGIC)
+            .filter(|d| d.len() >= NONCE_LEN)
+            .ok_or(CryptoError::InvalidFormat)?;
+
+        let (nonce, ciphertext) = data.split_at(NONCE_LEN);
+        let cipher = ChaCha20Poly1305::new(&self.0);
+        let aad = associated_data(day);
+
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: aad.as_bytes(),
+                },
+            )
+            .map_err(|_| CryptoError::DecryptionFailed)?;
+
+        String::from_utf8(plaintext).map_err(|_| CryptoError::InvalidUtf8)
+    }
+}
+
+fn associated_data(day: u32) -> String {
+    format!("advent_of_code_2023 day {:02}", day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let key = InputKey::generate();
+        let input = "seeds: 79 14 55 13\n\nünïcödé";
+
+        let encrypted = key.encrypt(5, input);
+        assert!(encrypted.starts_with(MAGIC));
+        assert_eq!(key.decrypt(5, &encrypted), Ok(input.to_string()));
+
+        // A fresh nonce is used every time
+        assert_ne!(encrypted, key.encrypt(5, input));
+    }
+
+    #[test]
+    fn rejects_wrong_key_day_or_tampering() {
+        let key = InputKey::generate();
+        let mut encrypted = key.encrypt(1, "1abc2");
+
+        assert_eq!(
+            InputKey::generate().decrypt(1, &encrypted),
+            Err(CryptoError::DecryptionFailed)
+        );
+        assert_eq!(
+            key.decrypt(2, &encrypted),
+            Err(CryptoError::DecryptionFailed)
+        );
+
+        *encrypted.last_mut().unwrap() ^= 1;
+        assert_eq!(
+            key.decrypt(1, &encrypted),
+            Err(CryptoError::DecryptionFailed)
+        );
+
+        assert_eq!(key.decrypt(1, b"1abc2"), Err(CryptoError::InvalidFormat));
+        assert_eq!(
+            key.decrypt(1, b"AOC1short"),
+            Err(CryptoError::InvalidFormat)
+        );
+    }
+
+    #[test]
+    fn hex_keys() {
+        let key = InputKey::generate();
+        assert_eq!(InputKey::from_hex(&key.to_hex()), Ok(key.clone()));
+        assert_eq!(InputKey::from_hex(&format!("{}\n", key.to_hex())), Ok(key));
+
+        assert_eq!(InputKey::from_hex("abcd"), Err(CryptoError::InvalidKey));
+        assert_eq!(
+            InputKey::from_hex(&"zz".repeat(32)),
+            Err(CryptoError::InvalidKey)
+        );
+        assert_eq!(
+            InputKey::from_hex(&"é".repeat(32)),
+            Err(CryptoError::InvalidKey)
+        );
+    }
+}
@@ -0,0 +1,535 @@
+crate::day!(1);
+
+use std::{
+    fmt::{self, Write},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::{
+    automaton::{AhoCorasick, Match},
+    parallel, Part,
+};
+
+pub fn part_1(input: &str) -> Option<u32> {
+    input
+        .lines()
+        .map(extract_calibration_value_1)
+        .sum::<Result<_, _>>()
+        .ok()
+}
+
+pub fn part_2(input: &str) -> Option<u32> {
+    calibration_sum(input, Language::English.vocabulary()).ok()
+}
+
+/// Sums the calibration values of every line, reading digits with the given vocabulary
+pub fn calibration_sum(input: &str, vocabulary: &Vocabulary) -> Result<u32, CalibrationError> {
+    let lines: Vec<_> = input.lines().collect();
+
+    parallel::sum(&lines, |l| vocabulary.calibration_value(l))
+}
+
+/// Only looks at ASCII bytes, which is still correct for any UTF-8 input
+/// since the bytes of a multi-byte character are never in the ASCII range
+pub fn extract_calibration_value_1(line: &str) -> Result<u32, CalibrationError> {
+    let mut digits = line.bytes().filter(|c| c.is_ascii_digit()).peekable();
+
+    let first = *digits.peek().ok_or(CalibrationError::NoDigits)?;
+    let last = digits.last().unwrap();
+
+    Ok((first - b'0') as u32 * 10 + (last - b'0') as u32)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationError {
+    NoDigits,
+}
+
+impl fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrationError::NoDigits => write!(f, "no digits found"),
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// A digit found in a line, with its position as a byte range
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Digit {
+    pub value: u32,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Everything read from a single line, for explaining an answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineCalibration {
+    /// Every digit found, including overlapping ones, in order of where they end
+    pub digits: Vec<Digit>,
+    pub value: Result<u32, CalibrationError>,
+}
+
+/// The spelled out digits of the puzzle. Words may overlap, as in "oneight".
+pub const ENGLISH: &[(&str, u32)] = &[
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+pub const GERMAN: &[(&str, u32)] = &[
+    ("eins", 1),
+    ("zwei", 2),
+    ("drei", 3),
+    ("vier", 4),
+    ("fünf", 5),
+    ("sechs", 6),
+    ("sieben", 7),
+    ("acht", 8),
+    ("neun", 9),
+];
+
+pub const FRENCH: &[(&str, u32)] = &[
+    ("un", 1),
+    ("deux", 2),
+    ("trois", 3),
+    ("quatre", 4),
+    ("cinq", 5),
+    ("six", 6),
+    ("sept", 7),
+    ("huit", 8),
+    ("neuf", 9),
+];
+
+pub const SPANISH: &[(&str, u32)] = &[
+    ("uno", 1),
+    ("dos", 2),
+    ("tres", 3),
+    ("cuatro", 4),
+    ("cinco", 5),
+    ("seis", 6),
+    ("siete", 7),
+    ("ocho", 8),
+    ("nueve", 9),
+];
+
+/// A language with a built in table of spelled out digits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    English,
+    German,
+    French,
+    Spanish,
+}
+
+impl Language {
+    pub fn words(self) -> &'static [(&'static str, u32)] {
+        match self {
+            Language::English => ENGLISH,
+            Language::German => GERMAN,
+            Language::French => FRENCH,
+            Language::Spanish => SPANISH,
+        }
+    }
+
+    /// The vocabulary for the language, built the first time it is needed
+    pub fn vocabulary(self) -> &'static Vocabulary {
+        static VOCABULARIES: [OnceLock<Vocabulary>; 4] = [const { OnceLock::new() }; 4];
+
+        VOCABULARIES[self as usize].get_or_init(|| Vocabulary::new(self.words()))
+    }
+}
+
+impl FromStr for Language {
+    type Err = String;
+
+    /// Parses a language name or a locale such as `de` or `fr-CA`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let language = s.split(['-', '_']).next().unwrap_or_default();
+
+        match language.to_ascii_lowercase().as_str() {
+            "en" | "english" => Ok(Language::English),
+            "de" | "german" => Ok(Language::German),
+            "fr" | "french" => Ok(Language::French),
+            "es" | "spanish" => Ok(Language::Spanish),
+            _ => Err(format!("Unknown language `{}`", s)),
+        }
+    }
+}
+
+/// The tokens that are read as digits: the ASCII digits plus a table of words
+pub struct Vocabulary {
+    forward: AhoCorasick<u32>,
+    /// The same tokens spelled backwards, for finding the last digit by
+    /// scanning from the end of the line
+    backward: AhoCorasick<u32>,
+    /// The length in bytes of the longest token
+    longest: usize,
+}
+
+impl Vocabulary {
+    pub fn new(words: &[(&str, u32)]) -> Self {
+        const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+
+        let tokens: Vec<_> = DIGITS
+            .iter()
+            .zip(0..)
+            .chain(words.iter().map(|(word, digit)| (word, *digit)))
+            .map(|(token, digit)| (token.as_bytes(), digit))
+            .collect();
+
+        let reversed = tokens.iter().map(|(token, digit)| {
+            let token: Vec<_> = token.iter().rev().copied().collect();
+            (token, *digit)
+        });
+
+        Self {
+            forward: AhoCorasick::new(tokens.iter().copied()),
+            backward: AhoCorasick::new(reversed),
+            longest: tokens.iter().map(|(token, _)| token.len()).max().unwrap(),
+        }
+    }
+
+    /// Every digit in the line, including overlapping ones
+    pub fn digits<'a>(&'a self, line: &'a str) -> impl Iterator<Item = Match<'a, u32>> + 'a {
+        self.forward.find_overlapping(line.as_bytes())
+    }
+
+    /// Combines the first digit in the line (the one that starts first) with
+    /// the last (the one that ends last). When two tokens start or end at the
+    /// same place, the longer one wins.
+    ///
+    /// The first digit is found by scanning forwards from the start and the
+    /// last by scanning the reversed tokens backwards from the end, so
+    /// usually only a few bytes at each end of the line are read.
+    pub fn calibration_value(&self, line: &str) -> Result<u32, CalibrationError> {
+        let bytes = line.as_bytes();
+
+        let first = self.first_token(&self.forward, bytes.iter());
+        let last = self.first_token(&self.backward, bytes.iter().rev());
+
+        match (first, last) {
+            (Some(first), Some(last)) => Ok(first * 10 + last),
+            _ => Err(CalibrationError::NoDigits),
+        }
+    }
+
+    /// Finds the value of the token that starts first in the bytes
+    fn first_token<'a>(
+        &self,
+        automaton: &AhoCorasick<u32>,
+        bytes: impl Iterator<Item = &'a u8>,
+    ) -> Option<u32> {
+        let mut state = automaton.start();
+        // The start, length and value of the best token so far
+        let mut best: Option<(usize, usize, u32)> = None;
+
+        for (i, &byte) in bytes.enumerate() {
+            // Tokens ending from here on start too late to beat the best one
+            if best.is_some_and(|(start, _, _)| i >= start + self.longest) {
+                break;
+            }
+
+            state = automaton.next_state(state, byte);
+
+            for (length, &value) in automaton.outputs(state) {
+                let start = i + 1 - length;
+
+                if best.is_none_or(|(s, l, _)| start < s || (start == s && length > l)) {
+                    best = Some((start, length, value));
+                }
+            }
+        }
+
+        best.map(|(_, _, value)| value)
+    }
+
+    /// Like [`Vocabulary::calibration_value`], also collecting the digits that were found
+    pub fn calibrate(&self, line: &str) -> LineCalibration {
+        let digits = self
+            .digits(line)
+            .map(|m| Digit {
+                value: *m.value,
+                start: m.start,
+                end: m.end,
+            })
+            .collect();
+
+        LineCalibration {
+            digits,
+            value: self.calibration_value(line),
+        }
+    }
+}
+
+pub fn extract_calibration_value_2(line: &str) -> Result<u32, CalibrationError> {
+    Language::English.vocabulary().calibration_value(line)
+}
+
+/// Lists the digits found on every line and the value each line contributes
+pub fn explain(input: &str, part: Part) -> String {
+    static DIGITS_ONLY: OnceLock<Vocabulary> = OnceLock::new();
+
+    let vocabulary = match part {
+        Part::One => DIGITS_ONLY.get_or_init(|| Vocabulary::new(&[])),
+        Part::Two => Language::English.vocabulary(),
+    };
+
+    let mut explanation = String::new();
+    let mut total = 0;
+    let mut failed = 0;
+
+    for (i, line) in input.lines().enumerate() {
+        let calibration = vocabulary.calibrate(line);
+        let digits: Vec<_> = calibration
+            .digits
+            .iter()
+            .map(|d| {
+                let column = line[..d.start].chars().count() + 1;
+                format!("{} ({:?} at {})", d.value, &line[d.start..d.end], column)
+            })
+            .collect();
+
+        let result = match calibration.value {
+            Ok(value) => {
+                total += value;
+                value.to_string()
+            }
+            Err(e) => {
+                failed += 1;
+                format!("ERROR {}", e)
+            }
+        };
+
+        writeln!(
+            explanation,
+            "{:>5}  {}\n       digits: {}\n       value:  {}",
+            i + 1,
+            line,
+            digits.join(", "),
+            result
+        )
+        .unwrap();
+    }
+
+    write!(explanation, "Total: {}", total).unwrap();
+    if failed > 0 {
+        let plural = if failed == 1 { "" } else { "s" };
+        write!(explanation, " ({} line{} failed)", failed, plural).unwrap();
+    }
+
+    explanation
+}
+
+#[cfg(test)]
+mod vocabulary_tests {
+    use std::cmp::{self, Reverse};
+
+    use super::*;
+    use crate::testing::Rng;
+
+    /// A pseudo-random input mixing letters, digits and digit words, with at
+    /// least one digit on every line
+    fn generated_input(lines: usize, words: &[(&str, u32)]) -> String {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+
+        let mut input = String::new();
+
+        for _ in 0..lines {
+            let length = 10 + rng.below(40);
+            let digit_at = rng.below(length);
+
+            for i in 0..length {
+                match rng.below(8) {
+                    _ if i == digit_at => input.push(char::from(b'0' + rng.below(10) as u8)),
+                    0 | 1 => input += words[rng.below(words.len())].0,
+                    _ => input.push(char::from(b'a' + rng.below(26) as u8)),
+                }
+            }
+
+            input.push('\n');
+        }
+
+        input
+    }
+
+    /// Finds every token and keeps the outermost ones, as a reference for the
+    /// bidirectional scanner
+    fn full_scan(vocabulary: &Vocabulary, line: &str) -> Result<u32, CalibrationError> {
+        let mut digits = vocabulary.digits(line);
+
+        let first = digits.next().ok_or(CalibrationError::NoDigits)?;
+        let (first, last) = digits.fold((first, first), |(first, last), m| {
+            (
+                cmp::min_by_key(first, m, |m| (m.start, Reverse(m.end))),
+                cmp::max_by_key(last, m, |m| (m.end, Reverse(m.start))),
+            )
+        });
+
+        Ok(first.value * 10 + last.value)
+    }
+
+    /// The scanner part 2 used before the vocabulary, which collects each line
+    /// and matches every window of five characters, kept as a benchmark baseline
+    fn window_scan(line: &str) -> u32 {
+        let chars: Vec<_> = line.chars().collect();
+
+        let mut digits = Vec::new();
+
+        for i in -4..chars.len() as isize {
+            let min = i.clamp(0, chars.len() as isize);
+            let max = (i + 5).clamp(0, chars.len() as isize);
+            let window = &chars[min as usize..max as usize];
+
+            let digit = match window {
+                ['0'..='9', ..] => window[0].to_digit(10),
+                ['o', 'n', 'e', ..] => Some(1),
+                ['t', 'w', 'o', ..] => Some(2),
+                ['t', 'h', 'r', 'e', 'e'] => Some(3),
+                ['f', 'o', 'u', 'r', ..] => Some(4),
+                ['f', 'i', 'v', 'e', ..] => Some(5),
+                ['s', 'i', 'x', ..] => Some(6),
+                ['s', 'e', 'v', 'e', 'n'] => Some(7),
+                ['e', 'i', 'g', 'h', 't'] => Some(8),
+                ['n', 'i', 'n', 'e', ..] => Some(9),
+                _ => None,
+            };
+
+            if let Some(digit) = digit {
+                digits.push(digit);
+            }
+        }
+
+        *digits.first().unwrap() * 10 + *digits.last().unwrap()
+    }
+
+    #[test]
+    fn scans_from_both_ends() {
+        let nested = [("on", 7), ("one", 1), ("neon", 4), ("eon", 5)];
+
+        for words in [ENGLISH, FRENCH, GERMAN, &nested] {
+            let vocabulary = Vocabulary::new(words);
+
+            for line in generated_input(2_000, words).lines().chain(["", "xyz"]) {
+                assert_eq!(
+                    vocabulary.calibration_value(line),
+                    full_scan(&vocabulary, line),
+                    "{}",
+                    line
+                );
+            }
+        }
+
+        let english = Language::English.vocabulary();
+        for line in generated_input(2_000, ENGLISH).lines() {
+            assert_eq!(english.calibration_value(line), Ok(window_scan(line)));
+        }
+
+        let nested = Vocabulary::new(&nested);
+        assert_eq!(nested.calibration_value("oneon"), Ok(14));
+        assert_eq!(nested.calibration_value("xneonx"), Ok(44));
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_bidirectional_scan(b: &mut test::Bencher) {
+        let input = generated_input(100_000, ENGLISH);
+        let english = Language::English.vocabulary();
+
+        b.iter(|| {
+            test::black_box(&input)
+                .lines()
+                .map(|l| english.calibration_value(l).unwrap())
+                .sum::<u32>()
+        });
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_full_scan(b: &mut test::Bencher) {
+        let input = generated_input(100_000, ENGLISH);
+        let english = Language::English.vocabulary();
+
+        b.iter(|| {
+            test::black_box(&input)
+                .lines()
+                .map(|l| full_scan(english, l).unwrap())
+                .sum::<u32>()
+        });
+    }
+
+    #[cfg(feature = "bench")]
+    #[bench]
+    fn bench_window_scan(b: &mut test::Bencher) {
+        let input = generated_input(100_000, ENGLISH);
+
+        b.iter(|| {
+            test::black_box(&input)
+                .lines()
+                .map(window_scan)
+                .sum::<u32>()
+        });
+    }
+
+    #[test]
+    fn parses_languages() {
+        assert_eq!("de".parse(), Ok(Language::German));
+        assert_eq!("fr-CA".parse(), Ok(Language::French));
+        assert_eq!("es_MX".parse(), Ok(Language::Spanish));
+        assert_eq!("English".parse(), Ok(Language::English));
+        assert!("tlh".parse::<Language>().is_err());
+    }
+
+    #[test]
+    fn decodes_other_languages() {
+        let value =
+            |language: Language, line| language.vocabulary().calibration_value(line).unwrap();
+
+        assert_eq!(value(Language::German, "xfünfzweiundachtx"), 58);
+        assert_eq!(value(Language::French, "ah3deuxtroisept"), 37);
+        assert_eq!(value(Language::Spanish, "seisiete"), 67);
+
+        let custom = Vocabulary::new(&[("один", 1), ("два", 2), ("三", 3)]);
+        assert_eq!(custom.calibration_value("двадинодин三"), Ok(23));
+        assert_eq!(custom.calibration_value("x9"), Ok(99));
+    }
+
+    #[test]
+    fn reports_lines_without_digits() {
+        let english = Language::English.vocabulary();
+
+        assert_eq!(
+            english.calibration_value("abc"),
+            Err(CalibrationError::NoDigits)
+        );
+        assert_eq!(
+            extract_calibration_value_1("one"),
+            Err(CalibrationError::NoDigits)
+        );
+        assert_eq!(part_2("two1nine\nabc"), None);
+
+        let calibration = english.calibrate("zweightwo3");
+        assert_eq!(calibration.value, Ok(83));
+        assert_eq!(
+            calibration
+                .digits
+                .iter()
+                .map(|d| d.start)
+                .collect::<Vec<_>>(),
+            [2, 6, 9]
+        );
+
+        let explanation = explain("a1b\nxyz", Part::One);
+        assert!(explanation.contains("1 (\"1\" at 2)"));
+        assert!(explanation.contains("ERROR no digits found"));
+        assert!(explanation.ends_with("Total: 11 (1 line failed)"));
+    }
+}
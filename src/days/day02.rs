@@ -0,0 +1,988 @@
+crate::day!(2);
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+};
+
+use crate::parsing::{keyword_id, labelled, parse_uint};
+use nom::{bytes::complete::tag, character::complete::alpha1, multi::separated_list0, IResult};
+
+/// The bag described in the puzzle
+pub const PUZZLE_BAG: &[(&str, u64)] = &[("red", 12), ("green", 13), ("blue", 14)];
+
+/// Returns `None` if a game is invalid, in which case [`parse_games`] says why
+pub fn part_1(input: &str) -> Option<u64> {
+    let games = parse_games(input).ok()?;
+    let bag = Set::from_cubes(PUZZLE_BAG.iter().copied()).unwrap();
+    let sum = feasible_games(&games, &bag).map(|g| g.id as u64).sum();
+
+    Some(sum)
+}
+
+/// Returns `None` if a game is invalid or the answer does not fit in a `u64`
+pub fn part_2(input: &str) -> Option<u64> {
+    let games = parse_games(input).ok()?;
+    let alphabet = colours(&games);
+
+    games.iter().try_fold(0u64, |sum, g| {
+        sum.checked_add(g.minimum_set().power(&alphabet)?)
+    })
+}
+
+/// Checks that every game is valid, returning how many there are
+pub fn parse(input: &str) -> Result<usize, String> {
+    parse_games(input)
+        .map(|games| games.len())
+        .map_err(|e| e.to_string())
+}
+
+/// Every colour that appears in any of the games
+pub fn colours<'a>(games: &[Game<'a>]) -> BTreeSet<&'a str> {
+    games
+        .iter()
+        .flat_map(|g| &g.sets)
+        .flat_map(Set::colours)
+        .collect()
+}
+
+/// The games that could have been played with the given bag
+pub fn feasible_games<'a, 'b>(
+    games: &'a [Game<'b>],
+    bag: &'a Set,
+) -> impl Iterator<Item = &'a Game<'b>> {
+    games.iter().filter(move |g| g.is_possible_with(bag))
+}
+
+/// The smallest bag with which every game is possible
+pub fn minimal_bag<'a>(games: &[Game<'a>]) -> Set<'a> {
+    games
+        .iter()
+        .map(Game::minimum_set)
+        .fold(Set::default(), |bag, set| bag.max(&set))
+}
+
+/// For each colour on its own, the smallest number of cubes that keeps at
+/// least `n` games possible. Returns `None` if there are fewer than `n` games.
+pub fn tightest_limits<'a>(games: &[Game<'a>], n: usize) -> Option<Set<'a>> {
+    if n > games.len() {
+        return None;
+    }
+
+    let minimum_sets: Vec<_> = games.iter().map(Game::minimum_set).collect();
+    let nth_smallest = |colour| {
+        let mut counts: Vec<_> = minimum_sets.iter().map(|s| s.get(colour)).collect();
+        n.checked_sub(1)
+            .map_or(0, |i| *counts.select_nth_unstable(i).1)
+    };
+
+    let counts = colours(games)
+        .into_iter()
+        .map(|colour| (colour, nth_smallest(colour)))
+        .collect();
+
+    Some(Set { counts })
+}
+
+/// The χ² critical value with one degree of freedom at 95% confidence. A
+/// count is in the confidence interval if its profile log likelihood is
+/// within half of this of the maximum.
+const CHI_SQUARED_95: f64 = 3.841_458_820_694_124;
+
+/// The largest bag [`estimate_bag`] will consider. The search for each
+/// confidence interval takes time quadratic in the size of the bag.
+pub const MAX_ESTIMATED_BAG: u32 = 2_000;
+
+/// The estimated number of cubes of one colour in a bag
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Estimate {
+    /// The likelihood peaks at a bag of finite size
+    Finite {
+        /// The maximum likelihood estimate
+        mle: u32,
+        /// The lower end of the 95% confidence interval
+        lower: u32,
+        /// The upper end of the 95% confidence interval, or `None` if even
+        /// an infinitely large bag is in the interval
+        upper: Option<u32>,
+    },
+    /// No finite bag is more likely than an infinitely large one, i.e. than
+    /// drawing with replacement, so there is no estimate and the interval
+    /// only has a lower end
+    Unbounded {
+        /// The lower end of the 95% confidence interval, which is never
+        /// below the count in the minimal bag
+        lower: u32,
+    },
+}
+
+impl fmt::Display for Estimate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Estimate::Finite {
+                mle,
+                lower,
+                upper: Some(upper),
+            } => write!(f, "{} [{}, {}]", mle, lower, upper),
+            Estimate::Finite {
+                mle,
+                lower,
+                upper: None,
+            } => write!(f, "{} [{}, ∞]", mle, lower),
+            Estimate::Unbounded { lower } => write!(f, "unbounded [{}, ∞]", lower),
+        }
+    }
+}
+
+/// Estimates how many cubes of each colour in the alphabet are in the bag,
+/// treating each set as drawn without replacement from the same bag and the
+/// cubes as put back between sets. If an unbounded estimate's interval
+/// cannot be found within [`MAX_ESTIMATED_BAG`] cubes, the minimal bag is used
+/// as its lower end. Returns `None` if a finite estimate would need a larger
+/// search.
+pub fn estimate_bag<'a: 'b, 'b>(
+    sets: impl IntoIterator<Item = &'b Set<'a>>,
+    alphabet: &BTreeSet<&'a str>,
+) -> Option<BTreeMap<&'a str, Estimate>> {
+    let sets: Vec<_> = sets.into_iter().collect();
+    let minimum = sets.iter().fold(Set::default(), |bag, set| bag.max(set));
+    let smallest = alphabet
+        .iter()
+        .try_fold(0u32, |sum, colour| sum.checked_add(minimum.get(colour)))?;
+
+    // Start a few times larger than the smallest possible bag, and search
+    // further whenever the limit could change the answer
+    let mut limit = smallest.checked_mul(5)?.checked_add(50)?;
+
+    loop {
+        if limit > MAX_ESTIMATED_BAG {
+            return None;
+        }
+
+        let likelihood = Likelihood::new(&sets, alphabet, limit)?;
+        let estimates = match limit {
+            // Fall back to the minimal bag as the lower end of a one-sided
+            // interval, which holds however large the bag is
+            MAX_ESTIMATED_BAG => likelihood.estimates().or_else(|| likelihood.minimal())?,
+            _ => match likelihood.estimates() {
+                Some(estimates) => estimates,
+                None => {
+                    limit = limit.saturating_mul(2).min(MAX_ESTIMATED_BAG);
+                    continue;
+                }
+            },
+        };
+
+        return Some(alphabet.iter().copied().zip(estimates).collect());
+    }
+}
+
+/// The log likelihood of a list of sets under the multivariate hypergeometric
+/// distribution, split into a term per colour and a term for the bag size:
+/// `Σ_sets (Σ_colours ln C(n_colour, x_colour) - ln C(n_total, x_total))`
+struct Likelihood {
+    /// The term for each colour, indexed by the number of cubes of that colour
+    colours: Vec<Vec<f64>>,
+    /// The term for the bag size, indexed by the total number of cubes
+    totals: Vec<f64>,
+    /// The supremum of the log likelihood as the bag grows without bound,
+    /// where drawing without replacement becomes drawing with replacement
+    at_infinity: f64,
+    /// The fewest cubes of each colour that could have produced every set
+    minimum: Vec<u32>,
+    /// The largest bag to search
+    limit: u32,
+}
+
+impl Likelihood {
+    fn new(sets: &[&Set], alphabet: &BTreeSet<&str>, limit: u32) -> Option<Self> {
+        let counts: Vec<Vec<u32>> = alphabet
+            .iter()
+            .map(|colour| sets.iter().map(|set| set.get(colour)).collect())
+            .collect();
+        let sizes: Vec<u32> = (0..sets.len())
+            .map(|i| counts.iter().map(|c| c[i]).try_fold(0u32, u32::checked_add))
+            .collect::<Option<_>>()?;
+        let minimum: Vec<u32> = counts
+            .iter()
+            .map(|c| c.iter().copied().max().unwrap_or_default())
+            .collect();
+
+        let mut ln_factorials = vec![0.0; limit as usize + 1];
+        for n in 1..=limit as usize {
+            ln_factorials[n] = ln_factorials[n - 1] + (n as f64).ln();
+        }
+        let ln_factorial = |n: u32| ln_factorials[n as usize];
+        let ln_choose = |n: u32, k: u32| match n.checked_sub(k) {
+            Some(rest) => ln_factorial(n) - ln_factorial(k) - ln_factorial(rest),
+            None => f64::NEG_INFINITY,
+        };
+        let ln_likelihood =
+            |counts: &[u32], n: u32| counts.iter().map(|&k| ln_choose(n, k)).sum::<f64>();
+
+        // The multinomial log likelihood of the sets, with each colour's
+        // probability at its maximum likelihood estimate (its share of all cubes)
+        let drawn: Vec<f64> = counts
+            .iter()
+            .map(|c| c.iter().map(|&k| k as f64).sum())
+            .collect();
+        let all_drawn: f64 = drawn.iter().sum();
+        let at_infinity = sizes.iter().map(|&n| ln_factorial(n)).sum::<f64>()
+            - counts
+                .iter()
+                .flatten()
+                .map(|&k| ln_factorial(k))
+                .sum::<f64>()
+            + drawn
+                .iter()
+                .filter(|&&x| x > 0.0)
+                .map(|&x| x * (x / all_drawn).ln())
+                .sum::<f64>();
+
+        Some(Likelihood {
+            colours: counts
+                .iter()
+                .map(|c| (0..=limit).map(|n| ln_likelihood(c, n)).collect())
+                .collect(),
+            totals: (0..=limit).map(|n| -ln_likelihood(&sizes, n)).collect(),
+            at_infinity,
+            minimum,
+            limit,
+        })
+    }
+
+    /// Estimates each colour, or returns `None` if the limit is too small to
+    /// be sure of the estimates
+    fn estimates(&self) -> Option<Vec<Estimate>> {
+        let (max_ll, mle) = self.profile(None)?;
+
+        // Without a finite maximum, the interval is measured from the
+        // supremum at infinity instead
+        let unbounded = max_ll <= self.at_infinity + 1e-9;
+        if !unbounded && self.reaches_limit(&mle) {
+            return None;
+        }
+
+        // The profile likelihood tends to the supremum at infinity as a
+        // colour's count grows, so the interval only ends if that is outside it
+        let threshold = max_ll.max(self.at_infinity) - CHI_SQUARED_95 / 2.0;
+        let bounded_above = self.at_infinity < threshold;
+
+        (0..mle.len())
+            .map(|c| {
+                let profile = |n| self.profile(Some((c, n)));
+                let within = |n| profile(n).is_some_and(|(ll, _)| ll >= threshold);
+                // A count outside the interval might only be there because
+                // its best bag did not fit under the limit
+                let cramped = |n| profile(n).is_some_and(|(_, bag)| self.reaches_limit(&bag));
+
+                // The profile is not unimodal, since the other colours can only be
+                // split into whole cubes, so search the whole range from both ends
+                let others: u32 = self.minimum.iter().sum::<u32>() - self.minimum[c];
+                let largest = self.limit - others;
+
+                let lower = match unbounded {
+                    true => (self.minimum[c]..=largest).find(|&n| within(n))?,
+                    false => (self.minimum[c]..=mle[c])
+                        .find(|&n| within(n))
+                        .unwrap_or(mle[c]),
+                };
+                if lower > self.minimum[c] && cramped(lower - 1) {
+                    return None;
+                }
+
+                if unbounded {
+                    return Some(Estimate::Unbounded { lower });
+                }
+
+                let upper = if bounded_above {
+                    let upper = (mle[c]..=largest)
+                        .rev()
+                        .find(|&n| within(n))
+                        .unwrap_or(mle[c]);
+                    if upper == largest || cramped(upper + 1) {
+                        return None;
+                    }
+                    Some(upper)
+                } else {
+                    None
+                };
+
+                Some(Estimate::Finite {
+                    mle: mle[c],
+                    lower,
+                    upper,
+                })
+            })
+            .collect()
+    }
+
+    /// The minimal bag as the lower end of each interval, if no finite bag is
+    /// more likely than an infinitely large one
+    fn minimal(&self) -> Option<Vec<Estimate>> {
+        let (max_ll, _) = self.profile(None)?;
+
+        (max_ll <= self.at_infinity + 1e-9).then(|| {
+            self.minimum
+                .iter()
+                .map(|&lower| Estimate::Unbounded { lower })
+                .collect()
+        })
+    }
+
+    fn reaches_limit(&self, bag: &[u32]) -> bool {
+        bag.iter().sum::<u32>() >= self.limit
+    }
+
+    /// The highest log likelihood of any bag up to the limit, optionally with
+    /// the count of one colour fixed, along with the smallest bag reaching it.
+    /// Returns `None` if the fixed count leaves no possible bag.
+    fn profile(&self, fixed: Option<(usize, u32)>) -> Option<(f64, Vec<u32>)> {
+        let mut bag = self.minimum.clone();
+        if let Some((c, n)) = fixed {
+            if n < bag[c] {
+                return None;
+            }
+            bag[c] = n;
+        }
+
+        let mut total = bag.iter().try_fold(0u32, |sum, &n| sum.checked_add(n))?;
+        if total > self.limit {
+            return None;
+        }
+
+        let free: Vec<_> = (0..bag.len())
+            .filter(|&c| fixed.is_none_or(|(f, _)| f != c))
+            .collect();
+        let mut colours_ll: f64 = (0..bag.len())
+            .map(|c| self.colours[c][bag[c] as usize])
+            .sum();
+        let mut best = (colours_ll + self.totals[total as usize], bag.clone());
+
+        // Each colour's term is concave, so adding every cube to the colour
+        // that gains the most gives the best bag for each total in one pass
+        while total < self.limit {
+            let gain = |&c: &usize| {
+                let n = bag[c] as usize;
+                (self.colours[c][n + 1] - self.colours[c][n], c)
+            };
+            let Some((gain, c)) = free.iter().map(gain).max_by(|a, b| a.0.total_cmp(&b.0)) else {
+                break;
+            };
+
+            bag[c] += 1;
+            total += 1;
+            colours_ll += gain;
+
+            let ll = colours_ll + self.totals[total as usize];
+            if ll > best.0 + 1e-9 {
+                best = (ll, bag.clone());
+            }
+        }
+
+        Some(best)
+    }
+}
+
+/// Answers `--query` questions about the games:
+///
+/// - `feasible <bag>` lists the games possible with a bag such as `12 red, 13 green, 14 blue`
+/// - `minimal-bag` gives the smallest bag with which every game is possible
+/// - `tightest <n>` gives the smallest limit per colour that keeps `n` games possible
+/// - `estimate` estimates the bag with 95% confidence intervals, for each game
+///   and for all games together as if they were played with the same bag
+pub fn query(input: &str, args: &[String]) -> Result<String, String> {
+    let games = parse_games(input).map_err(|e| e.to_string())?;
+
+    match args {
+        [command, bag @ ..] if command == "feasible" => {
+            let bag = bag.join(" ");
+            let bag = parse_set(&bag).map_err(|e| format!("Invalid bag `{}`: {}", bag, e))?;
+
+            let ids: Vec<_> = feasible_games(&games, &bag).map(|g| g.id).collect();
+            let sum: u64 = ids.iter().map(|&id| id as u64).sum();
+
+            Ok(format!(
+                "{} of {} games are possible with {}: {:?} (sum of ids {})",
+                ids.len(),
+                games.len(),
+                bag,
+                ids,
+                sum
+            ))
+        }
+        [command] if command == "minimal-bag" => Ok(minimal_bag(&games).to_string()),
+        [command, n] if command == "tightest" => {
+            let n: usize = n
+                .parse()
+                .map_err(|_| format!("Invalid number of games `{}`", n))?;
+
+            tightest_limits(&games, n)
+                .map(|limits| limits.to_string())
+                .ok_or_else(|| format!("There are only {} games", games.len()))
+        }
+        [command] if command == "estimate" => {
+            let alphabet = colours(&games);
+            let describe = |estimates: BTreeMap<_, Estimate>| {
+                let colours: Vec<_> = estimates
+                    .iter()
+                    .map(|(colour, estimate)| format!("{} {}", colour, estimate))
+                    .collect();
+                colours.join(", ")
+            };
+            let too_large = || "Too many cubes to estimate the bag".to_string();
+
+            let mut lines = vec!["Maximum likelihood estimates [95% confidence intervals]:".to_string()];
+            for game in &games {
+                let estimates = estimate_bag(&game.sets, &alphabet).ok_or_else(too_large)?;
+                lines.push(format!("Game {}: {}", game.id, describe(estimates)));
+            }
+
+            let sets = games.iter().flat_map(|g| &g.sets);
+            let estimates = estimate_bag(sets, &alphabet).ok_or_else(too_large)?;
+            lines.push(format!("All games: {}", describe(estimates)));
+
+            Ok(lines.join("\n"))
+        }
+        _ => Err(
+            "Usage: --query feasible <bag> | --query minimal-bag | --query tightest <n> | --query estimate"
+                .to_string(),
+        ),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Game<'a> {
+    pub id: u32,
+    pub sets: Vec<Set<'a>>,
+}
+
+impl<'a> Game<'a> {
+    /// Whether every set drawn in the game fits in the bag
+    pub fn is_possible_with(&self, bag: &Set) -> bool {
+        self.sets.iter().all(|set| bag.contains(set))
+    }
+
+    /// The fewest cubes of each colour that could have produced every set
+    pub fn minimum_set(&self) -> Set<'a> {
+        self.sets
+            .iter()
+            .fold(Set::default(), |minimum_set, set| minimum_set.max(set))
+    }
+}
+
+/// A number of cubes of each colour. Colours that are not in the set count as zero.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Set<'a> {
+    counts: BTreeMap<&'a str, u32>,
+}
+
+impl<'a> Set<'a> {
+    pub fn get(&self, colour: &str) -> u32 {
+        self.counts.get(colour).copied().unwrap_or_default()
+    }
+
+    /// Builds a set from a list of cubes, adding up the counts of colours that
+    /// appear more than once. Returns the colour whose count overflows, if any.
+    pub fn from_cubes(cubes: impl IntoIterator<Item = (&'a str, u64)>) -> Result<Self, &'a str> {
+        let mut set = Set::default();
+
+        for (colour, count) in cubes {
+            u32::try_from(count)
+                .ok()
+                .and_then(|count| set.checked_add(colour, count))
+                .ok_or(colour)?;
+        }
+
+        Ok(set)
+    }
+
+    /// Adds cubes of a colour, returning the new count, or `None` (leaving the
+    /// set unchanged) if it would overflow
+    pub fn checked_add(&mut self, colour: &'a str, count: u32) -> Option<u32> {
+        let total = self.counts.entry(colour).or_default();
+        *total = total.checked_add(count)?;
+
+        Some(*total)
+    }
+
+    /// The colours in the set, in alphabetical order
+    pub fn colours(&self) -> impl Iterator<Item = &'a str> + '_ {
+        self.counts.keys().copied()
+    }
+
+    /// The product of the counts of every colour in the alphabet, or `None`
+    /// if it does not fit in a `u64`
+    pub fn power(&self, alphabet: &BTreeSet<&str>) -> Option<u64> {
+        alphabet.iter().try_fold(1u64, |power, colour| {
+            power.checked_mul(self.get(colour) as u64)
+        })
+    }
+
+    /// Whether this set has at least as many cubes of every colour as `other`
+    pub fn contains(&self, other: &Set) -> bool {
+        other
+            .counts
+            .iter()
+            .all(|(colour, &count)| self.get(colour) >= count)
+    }
+
+    /// The larger count of each colour
+    pub fn max(&self, other: &Set<'a>) -> Set<'a> {
+        let mut max = self.clone();
+
+        for (&colour, &count) in &other.counts {
+            let entry = max.counts.entry(colour).or_default();
+            *entry = (*entry).max(count);
+        }
+
+        max
+    }
+}
+
+impl fmt::Display for Set<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let cubes: Vec<_> = self
+            .counts
+            .iter()
+            .map(|(colour, count)| format!("{} {}", count, colour))
+            .collect();
+
+        f.write_str(&cubes.join(", "))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// A line is not in the format `Game <id>: <count> <colour>, ...; ...`
+    Syntax { line: usize },
+    /// The cubes of one colour in a single draw add up to more than `u32::MAX`
+    Overflow { line: usize, colour: String },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax { line } => write!(f, "Line {} is not a valid game", line),
+            ParseError::Overflow { line, colour } => write!(
+                f,
+                "Line {} has more than {} {} cubes in one draw",
+                line,
+                u32::MAX,
+                colour
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Why a set on its own, such as a bag given on the command line, is invalid
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SetError {
+    /// The set is not in the format `<count> <colour>, ...`
+    Syntax,
+    /// The cubes of one colour add up to more than `u32::MAX`
+    Overflow { colour: String },
+}
+
+impl fmt::Display for SetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SetError::Syntax => write!(f, "expected cubes such as `3 blue, 4 red`"),
+            SetError::Overflow { colour } => {
+                write!(f, "more than {} {} cubes", u32::MAX, colour)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SetError {}
+
+pub fn parse_games(input: &str) -> Result<Vec<Game<'_>>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_game(line, i + 1))
+        .collect()
+}
+
+/// Parses a line such as `Game 1: 3 blue, 4 red; 1 red, 2 green`
+pub fn parse_game(line: &str, line_number: usize) -> Result<Game<'_>, ParseError> {
+    let Ok(("", (id, draws))) =
+        labelled(keyword_id("Game"), separated_list0(tag("; "), parse_draw))(line)
+    else {
+        return Err(ParseError::Syntax { line: line_number });
+    };
+
+    let sets = draws
+        .into_iter()
+        .map(|cubes| {
+            Set::from_cubes(cubes).map_err(|colour| ParseError::Overflow {
+                line: line_number,
+                colour: colour.to_string(),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Game { id, sets })
+}
+
+/// Parses a set such as `3 blue, 4 red` on its own. A colour may appear more
+/// than once, in which case the counts are added up.
+pub fn parse_set(input: &str) -> Result<Set<'_>, SetError> {
+    let Ok(("", cubes)) = parse_draw(input) else {
+        return Err(SetError::Syntax);
+    };
+
+    Set::from_cubes(cubes).map_err(|colour| SetError::Overflow {
+        colour: colour.to_string(),
+    })
+}
+
+/// Parses the cubes of one draw, before they are added up by colour. Counts
+/// are read as `u64` so that those too large for a [`Set`] are reported as
+/// overflows rather than syntax errors.
+fn parse_draw(input: &str) -> IResult<&str, Vec<(&str, u64)>> {
+    separated_list0(tag(", "), parse_cube)(input)
+}
+
+fn parse_cube(input: &str) -> IResult<&str, (&str, u64)> {
+    let (input, num) = parse_uint(input)?;
+    let (input, _) = tag(" ")(input)?;
+    let (input, colour) = alpha1(input)?;
+
+    Ok((input, (colour, num)))
+}
+
+#[cfg(test)]
+mod bag_tests {
+    use super::*;
+    use crate::testing::Rng;
+
+    fn set<'a>(cubes: &[(&'a str, u64)]) -> Set<'a> {
+        Set::from_cubes(cubes.iter().copied()).unwrap()
+    }
+
+    fn example_input() -> String {
+        let (_, input) = crate::read_example_file_with::<u32>(DAY, crate::Part::One, NORMALIZE);
+        input
+    }
+
+    #[test]
+    fn answers_bag_queries() {
+        let input = example_input();
+        let games = parse_games(&input).unwrap();
+        let bag = |red, green, blue| set(&[("red", red), ("green", green), ("blue", blue)]);
+
+        let puzzle_bag = set(PUZZLE_BAG);
+        let ids: Vec<_> = feasible_games(&games, &puzzle_bag).map(|g| g.id).collect();
+        assert_eq!(ids, [1, 2, 5]);
+
+        let ids: Vec<_> = feasible_games(&games, &bag(6, 3, 6))
+            .map(|g| g.id)
+            .collect();
+        assert_eq!(ids, [1, 2, 5]);
+        assert_eq!(feasible_games(&games, &bag(4, 3, 5)).count(), 1);
+
+        assert_eq!(minimal_bag(&games), bag(20, 13, 15));
+        assert_eq!(tightest_limits(&games, 3), Some(bag(6, 3, 6)));
+        assert_eq!(tightest_limits(&games, 1), Some(bag(1, 2, 2)));
+        assert_eq!(tightest_limits(&games, 5), Some(minimal_bag(&games)));
+        assert_eq!(tightest_limits(&games, 0), Some(bag(0, 0, 0)));
+        assert_eq!(tightest_limits(&games, 6), None);
+    }
+
+    #[test]
+    fn parses_queries() {
+        let input = "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green";
+        let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+
+        assert_eq!(
+            query(
+                input,
+                &args(&["feasible", "4", "red,", "2", "green,", "6", "blue"])
+            ),
+            Ok("1 of 1 games are possible with 6 blue, 2 green, 4 red: [1] (sum of ids 1)".into())
+        );
+        assert_eq!(
+            query(input, &args(&["minimal-bag"])),
+            Ok("6 blue, 2 green, 4 red".into())
+        );
+        assert_eq!(
+            query(input, &args(&["feasible", "lots"])),
+            Err("Invalid bag `lots`: expected cubes such as `3 blue, 4 red`".into())
+        );
+        assert_eq!(
+            query(input, &args(&["feasible", "4294967296", "red"])),
+            Err("Invalid bag `4294967296 red`: more than 4294967295 red cubes".into())
+        );
+        assert_eq!(
+            query(input, &args(&["estimate"])),
+            Ok("Maximum likelihood estimates [95% confidence intervals]:\n\
+                Game 1: blue unbounded [7, ∞], green unbounded [2, ∞], red unbounded [4, ∞]\n\
+                All games: blue unbounded [7, ∞], green unbounded [2, ∞], red unbounded [4, ∞]"
+                .into())
+        );
+        assert_eq!(
+            query(
+                "Game 2: 1 blue, 2 green; 3 green, 4 blue, 1 red; 1 green, 1 blue",
+                &args(&["estimate"])
+            ),
+            Ok("Maximum likelihood estimates [95% confidence intervals]:\n\
+                Game 2: blue 4 [4, 45], green 3 [3, 45], red 1 [1, 7]\n\
+                All games: blue 4 [4, 45], green 3 [3, 45], red 1 [1, 7]"
+                .into())
+        );
+        assert!(query(input, &args(&["tightest", "two"])).is_err());
+        assert!(query(input, &args(&["unknown"])).is_err());
+    }
+
+    #[test]
+    fn handles_any_colours() {
+        let input = "\
+Game 1: 2 teal, 1 red, 3 teal; 4 mauve
+Game 2: 1 red, 1 mauve
+Game 3: 5 red";
+        let games = parse_games(input).unwrap();
+
+        assert_eq!(games[0].sets[0], set(&[("teal", 5), ("red", 1)]));
+        assert_eq!(
+            colours(&games).into_iter().collect::<Vec<_>>(),
+            ["mauve", "red", "teal"]
+        );
+        assert_eq!(
+            games[0].minimum_set(),
+            set(&[("teal", 5), ("red", 1), ("mauve", 4)])
+        );
+        assert_eq!(part_2(input), Some(20));
+
+        let bag = set(&[("red", 5), ("mauve", 1)]);
+        let ids: Vec<_> = feasible_games(&games, &bag).map(|g| g.id).collect();
+        assert_eq!(ids, [2, 3]);
+        assert_eq!(minimal_bag(&games).to_string(), "4 mauve, 5 red, 5 teal");
+    }
+
+    #[test]
+    fn reports_overflowing_counts() {
+        let max = u32::MAX;
+
+        let games = parse_games("Game 1: 4294967295 red, 0 red; 1 blue").unwrap();
+        assert_eq!(games[0].sets[0].get("red"), max);
+
+        assert_eq!(
+            parse_games("Game 1: 1 red\nGame 2: 4294967295 red, 1 red").unwrap_err(),
+            ParseError::Overflow {
+                line: 2,
+                colour: "red".into()
+            }
+        );
+        assert_eq!(
+            parse_games("Game 7: 4294967296 green").unwrap_err(),
+            ParseError::Overflow {
+                line: 1,
+                colour: "green".into()
+            }
+        );
+        assert_eq!(
+            parse_games("Game 1: 1 red\nGame x: 1 red").unwrap_err(),
+            ParseError::Syntax { line: 2 }
+        );
+
+        assert_eq!(part_1("Game 1: 4294967296 green"), None);
+        assert_eq!(part_2("Game 1: 1 red\nGame 2 1 red"), None);
+
+        let mut full = Set::default();
+        assert_eq!(full.checked_add("red", max), Some(max));
+        assert_eq!(full.checked_add("red", 1), None);
+        assert_eq!(full.get("red"), max);
+
+        let alphabet = ["red", "green", "blue"].into();
+        let huge = set(&[("red", max as u64), ("green", max as u64), ("blue", 2)]);
+        assert_eq!(huge.power(&alphabet), None);
+    }
+
+    #[test]
+    fn estimates_bags() {
+        let alphabet: BTreeSet<_> = ["red", "green", "blue"].into();
+
+        // A single draw is most likely to have emptied the bag
+        let single = [set(&[("red", 3), ("green", 1)])];
+        let estimates = estimate_bag(&single, &alphabet).unwrap();
+        assert!(matches!(
+            estimates["red"],
+            Estimate::Finite {
+                mle: 3,
+                lower: 3,
+                ..
+            }
+        ));
+        assert!(matches!(estimates["blue"], Estimate::Finite { mle: 0, .. }));
+
+        // Draws this different are likelier with replacement than from any
+        // finite bag, so only the lower end of each interval is known
+        let apart = [set(&[("red", 3)]), set(&[("blue", 3)])];
+        let estimates = estimate_bag(&apart, &alphabet).unwrap();
+        assert!(estimates
+            .values()
+            .all(|e| matches!(e, Estimate::Unbounded { .. })));
+        assert_eq!(estimates["red"], Estimate::Unbounded { lower: 3 });
+        assert_eq!(estimates["red"].to_string(), "unbounded [3, ∞]");
+
+        let input = example_input();
+        let games = parse_games(&input).unwrap();
+        for game in &games {
+            let minimum = game.minimum_set();
+            for (colour, estimate) in estimate_bag(&game.sets, &alphabet).unwrap() {
+                match estimate {
+                    Estimate::Finite { mle, lower, upper } => {
+                        assert!(minimum.get(colour) <= lower && lower <= mle);
+                        assert!(upper.is_none_or(|upper| mle <= upper));
+                    }
+                    Estimate::Unbounded { lower } => assert!(minimum.get(colour) <= lower),
+                }
+            }
+        }
+
+        let huge = [set(&[("red", u32::MAX as u64)])];
+        assert_eq!(estimate_bag(&huge, &alphabet), None);
+
+        // Counts drawn uniformly at random look like drawing with replacement
+        // from a bag larger than the search covers, leaving only the minimal bag
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+        let uniform: Vec<_> = (0..400)
+            .map(|_| {
+                let mut count = || rng.below(21) as u64;
+                set(&[("red", count()), ("green", count()), ("blue", count())])
+            })
+            .collect();
+        let minimum = uniform.iter().fold(Set::default(), |bag, set| bag.max(set));
+        for (colour, estimate) in estimate_bag(&uniform, &alphabet).unwrap() {
+            assert_eq!(
+                estimate,
+                Estimate::Unbounded {
+                    lower: minimum.get(colour)
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn estimates_example_bags() {
+        let input = example_input();
+        let args = ["estimate".to_string()];
+
+        assert_eq!(
+            query(&input, &args),
+            Ok("Maximum likelihood estimates [95% confidence intervals]:\n\
+                Game 1: blue unbounded [7, ∞], green unbounded [2, ∞], red unbounded [4, ∞]\n\
+                Game 2: blue 4 [4, 45], green 3 [3, 45], red 1 [1, 7]\n\
+                Game 3: blue unbounded [13, ∞], green unbounded [31, ∞], red unbounded [34, ∞]\n\
+                Game 4: blue 15 [15, ∞], green 3 [3, ∞], red 14 [14, ∞]\n\
+                Game 5: blue 2 [2, ∞], green 3 [3, ∞], red 6 [6, ∞]\n\
+                All games: blue unbounded [57, ∞], green unbounded [54, ∞], red unbounded [71, ∞]"
+                .into())
+        );
+    }
+
+    #[test]
+    fn recovers_simulated_bags() {
+        let mut rng = Rng::new(0x9876_5432_1fed_cba9);
+
+        let bag = [("red", 10), ("green", 25), ("blue", 40)];
+        let mut cubes: Vec<_> = bag
+            .iter()
+            .flat_map(|&(colour, count)| std::iter::repeat_n(colour, count))
+            .collect();
+
+        // Draw 20 cubes without replacement, putting them back between draws
+        let sets: Vec<_> = (0..300)
+            .map(|_| {
+                for i in 0..20 {
+                    let j = i + rng.below(cubes.len() - i);
+                    cubes.swap(i, j);
+                }
+                Set::from_cubes(cubes[..20].iter().map(|&colour| (colour, 1))).unwrap()
+            })
+            .collect();
+
+        let alphabet = bag.iter().map(|&(colour, _)| colour).collect();
+        let estimates = estimate_bag(&sets, &alphabet).unwrap();
+
+        for (colour, count) in bag {
+            let Estimate::Finite {
+                mle,
+                lower,
+                upper: Some(upper),
+            } = estimates[colour]
+            else {
+                panic!("{} should have a finite interval", colour);
+            };
+
+            assert!(lower as usize <= count && count <= upper as usize);
+            assert!((mle as f64 - count as f64).abs() <= count as f64 * 0.3);
+        }
+    }
+
+    /// Generates `count` games with ids well past 255 and counts of up to
+    /// `max_count`, where some draws name a colour twice. Returns the input
+    /// along with the part 1 and part 2 answers worked out while generating it.
+    fn synthetic_games(count: u32, max_count: u32) -> (String, u64, u64) {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+        let mut random = |bound: u32| rng.below(bound as usize) as u32;
+
+        let colours = ["red", "green", "blue"];
+        let mut input = String::new();
+        let (mut part_1, mut part_2) = (0, 0);
+
+        for id in 1..=count {
+            // Keep about half of the games small enough to be possible
+            let limit = if random(2) == 0 { 15 } else { max_count };
+            let mut minimum = [0u64; 3];
+            let mut draws = Vec::new();
+
+            for _ in 0..1 + random(4) {
+                let mut cubes = Vec::new();
+
+                for (c, colour) in colours.iter().enumerate() {
+                    let total = random(limit);
+                    minimum[c] = minimum[c].max(total as u64);
+
+                    // Split some counts in two and leave out some colours with no cubes
+                    match (random(3), total) {
+                        (0, 0) => {}
+                        (0, total) if total > 1 => {
+                            cubes.push(format!("{} {}", total / 2, colour));
+                            cubes.push(format!("{} {}", total - total / 2, colour));
+                        }
+                        _ => cubes.push(format!("{} {}", total, colour)),
+                    }
+                }
+
+                draws.push(cubes.join(", "));
+            }
+
+            if minimum[0] <= 12 && minimum[1] <= 13 && minimum[2] <= 14 {
+                part_1 += id as u64;
+            }
+            part_2 += minimum.iter().product::<u64>();
+
+            input += &format!("Game {}: {}\n", id, draws.join("; "));
+        }
+
+        input.pop();
+        (input, part_1, part_2)
+    }
+
+    #[test]
+    fn solves_large_synthetic_games() {
+        let (input, part_1_answer, part_2_answer) = synthetic_games(20_000, 100_000);
+
+        assert_eq!(part_1(&input), Some(part_1_answer));
+        assert_eq!(part_2(&input), Some(part_2_answer));
+
+        let games = parse_games(&input).unwrap();
+        assert_eq!(games.last().unwrap().id, 20_000);
+        assert!(minimal_bag(&games).get("red") > 255);
+    }
+}
@@ -0,0 +1,126 @@
+crate::day!(4);
+
+use std::{collections::HashMap, fmt};
+
+use crate::{
+    parallel,
+    parsing::{keyword_id, labelled, uint_list},
+};
+use nom::{bytes::complete::tag, character::complete::space1, sequence::tuple, IResult};
+
+/// Returns `None` if a card is invalid, in which case [`parse_cards`] says why
+pub fn part_1(input: &str) -> Option<u32> {
+    let cards = parse_cards(input).ok()?;
+
+    Some(cards.iter().map(Card::score).sum())
+}
+
+/// Returns `None` if a card is invalid, in which case [`parse_cards`] says why
+pub fn part_2(input: &str) -> Option<u32> {
+    let original_cards = parse_cards(input).ok()?;
+
+    let mut solution: HashMap<u32, u32> = HashMap::new();
+
+    original_cards.iter().for_each(|card| {
+        // Include the original counts for each card id
+        *solution.entry(card.id).or_insert(0) += 1;
+
+        // Get the current count for the card
+        let current_amount = solution[&card.id];
+
+        // For each match, add our current amount to that card. This works becauase
+        // adding the current amount is like iterating it but cheaper.
+        for i in 0..card.matches {
+            *solution.entry(card.id + 1 + i).or_insert(0) += current_amount;
+        }
+    });
+
+    Some(solution.values().sum())
+}
+
+/// Checks that every card is valid, returning how many there are
+pub fn parse(input: &str) -> Result<usize, String> {
+    parse_cards(input)
+        .map(|cards| cards.len())
+        .map_err(|e| e.to_string())
+}
+
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub id: u32,
+    pub matches: u32,
+}
+
+impl Card {
+    pub fn new(id: u32, winning_numbers: Vec<u32>, my_numbers: Vec<u32>) -> Self {
+        // OPTIMIZATION: Cache matches in constructor
+        let matches = winning_numbers
+            .iter()
+            .filter(|w| my_numbers.contains(w))
+            .count() as u32;
+
+        Self { id, matches }
+    }
+
+    pub fn score(&self) -> u32 {
+        if self.matches == 0 {
+            return 0;
+        }
+
+        2u32.pow(self.matches - 1)
+    }
+}
+
+/// A line of the input that is not a card
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {} is not a valid card", self.line)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub fn parse_cards(input: &str) -> Result<Vec<Card>, ParseError> {
+    // OPTIMIZATION: Instead of using nom to parse out the newline separators,
+    // we can parse in parallel which spreads out card `matches` compute
+    let lines: Vec<_> = input.lines().enumerate().collect();
+
+    parallel::map(lines, |(i, l)| match parse_card(l) {
+        Ok(("", card)) => Ok(card),
+        _ => Err(ParseError { line: i + 1 }),
+    })
+    .into_iter()
+    .collect()
+}
+
+pub fn parse_card(input: &str) -> IResult<&str, Card> {
+    let (input, (id, (winning_numbers, _, _, _, my_numbers))) = labelled(
+        keyword_id("Card"),
+        tuple((uint_list, space1, tag("|"), space1, uint_list)),
+    )(input)?;
+
+    Ok((input, Card::new(id, winning_numbers, my_numbers)))
+}
+
+#[cfg(test)]
+mod card_tests {
+    use super::*;
+
+    #[test]
+    fn rejects_invalid_cards() {
+        let input = "\
+Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61
+Card three: 1 | 2";
+
+        assert_eq!(parse_cards(input).unwrap_err(), ParseError { line: 3 });
+        assert_eq!(part_1(input), None);
+        assert_eq!(part_2(input), None);
+        assert_eq!(parse(input), Err("Line 3 is not a valid card".to_string()));
+    }
+}
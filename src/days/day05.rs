@@ -0,0 +1,95 @@
+crate::day!(5);
+
+use crate::{
+    interval::{IntervalMap, IntervalSet, OverlapError},
+    parsing::{int_list, labelled, list, parse_int},
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alpha1, newline, space1},
+    combinator::map_res,
+    multi::{count, separated_list0},
+    sequence::tuple,
+    IResult,
+};
+
+pub fn part_1(input: &str) -> Option<i64> {
+    let (input, (seeds, maps)) = parse_input(input, parse_seed_list).unwrap();
+
+    assert_eq!(input, "");
+
+    let locations = seeds
+        .iter()
+        .map(|seed| maps.iter().fold(*seed, |acc, m| m.apply(acc)));
+
+    locations.min()
+}
+
+pub fn part_2(input: &str) -> Option<i64> {
+    let (input, (seeds, maps)) = parse_input(input, parse_seed_range_list).unwrap();
+
+    assert_eq!(input, "");
+
+    let locations = maps
+        .iter()
+        .fold(seeds, |ranges, map| map.apply_set(&ranges));
+
+    locations.min()
+}
+
+pub type Map = IntervalMap<i64>;
+
+pub fn parse_input<T>(
+    input: &str,
+    seed_parsing_fn: fn(&str) -> IResult<&str, T>,
+) -> IResult<&str, (T, Vec<Map>)> {
+    let (input, seeds) = seed_parsing_fn(input)?;
+    let (input, _) = count(newline, 2)(input)?;
+    let (input, maps) = separated_list0(count(newline, 2), parse_map)(input)?;
+
+    Ok((input, (seeds, maps)))
+}
+
+pub fn parse_seed_list(input: &str) -> IResult<&str, Vec<i64>> {
+    let (input, (_, seeds)) = labelled(tag("seeds"), int_list)(input)?;
+
+    Ok((input, seeds))
+}
+
+pub fn parse_seed_range_list(input: &str) -> IResult<&str, IntervalSet<i64>> {
+    let (input, (_, seeds)) = labelled(
+        tag("seeds"),
+        list(tuple((parse_int, space1, parse_int::<i64>))),
+    )(input)?;
+
+    let seeds = seeds
+        .iter()
+        .map(|(start, _, length)| *start..(start + length))
+        .collect();
+
+    Ok((input, seeds))
+}
+
+pub fn parse_map(input: &str) -> IResult<&str, Map> {
+    let (input, _) = tuple((alpha1, tag("-to-"), alpha1, tag(" map:"), newline))(input)?;
+
+    map_res(separated_list0(newline, parse_map_range), |ranges| {
+        let mut map = Map::new();
+
+        for (dest_range_start, src_range_start, range_length) in ranges {
+            map.insert(
+                src_range_start..src_range_start + range_length,
+                dest_range_start,
+            )?;
+        }
+
+        Ok::<_, OverlapError>(map)
+    })(input)
+}
+
+fn parse_map_range(input: &str) -> IResult<&str, (i64, i64, i64)> {
+    let (input, (dest_range_start, _, src_range_start, _, range_length)) =
+        tuple((parse_int, space1, parse_int, space1, parse_int))(input)?;
+
+    Ok((input, (dest_range_start, src_range_start, range_length)))
+}
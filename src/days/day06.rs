@@ -0,0 +1,102 @@
+use crate::{
+    math::quadratic_below_zero,
+    parsing::{labelled, list, uint_list},
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{digit1, newline},
+    IResult,
+};
+
+crate::day!(6);
+
+pub fn part_1(input: &str) -> Option<u64> {
+    let (input, races) = parse_input_as_list(input).unwrap();
+
+    assert_eq!(input, "");
+
+    races.iter().try_fold(1u64, |product, race| {
+        product.checked_mul(race.get_num_solutions()?)
+    })
+}
+
+pub fn part_2(input: &str) -> Option<u64> {
+    let (input, race) = parse_input_as_single_race(input).unwrap();
+
+    assert_eq!(input, "");
+
+    race.get_num_solutions()
+}
+
+#[derive(Debug)]
+pub struct Race {
+    pub race_time: u64,
+    pub record_distance: u64,
+}
+
+impl Race {
+    /// Finds the number of races in which we can beat the record by treating
+    /// the distance as a function of the charge time and solving the inequality
+    /// `(race_time - x) * x > record_distance`, which rearranges to
+    /// `x^2 - race_time * x + record_distance < 0`
+    ///
+    /// This is orders of magnitude more efficient than simply trying every possible
+    /// value in the input range, and is exact since it never goes through floats.
+    /// Returns `None` if the numbers are too large to solve exactly.
+    pub fn get_num_solutions(&self) -> Option<u64> {
+        let winning_times =
+            quadratic_below_zero(1, -(self.race_time as i128), self.record_distance as i128)?;
+
+        match winning_times.is_empty() {
+            true => Some(0),
+            false => Some((winning_times.end() - winning_times.start() + 1) as u64),
+        }
+    }
+}
+
+// ================= PART 1 =================
+
+pub fn parse_input_as_list(input: &str) -> IResult<&str, Vec<Race>> {
+    let (input, times) = parse_line_as_list("Time", input)?;
+    let (input, _) = newline(input)?;
+    let (input, distances) = parse_line_as_list("Distance", input)?;
+
+    let races: Vec<_> = times
+        .into_iter()
+        .zip(distances)
+        .map(|t| Race {
+            race_time: t.0,
+            record_distance: t.1,
+        })
+        .collect();
+
+    Ok((input, races))
+}
+
+fn parse_line_as_list<'a>(tag_str: &'a str, input: &'a str) -> IResult<&'a str, Vec<u64>> {
+    let (input, (_, ints)) = labelled(tag(tag_str), uint_list)(input)?;
+
+    Ok((input, ints))
+}
+
+// ================= PART 2 =================
+
+pub fn parse_input_as_single_race(input: &str) -> IResult<&str, Race> {
+    let (input, race_time) = parse_line_as_int("Time", input)?;
+    let (input, _) = newline(input)?;
+    let (input, record_distance) = parse_line_as_int("Distance", input)?;
+
+    Ok((
+        input,
+        Race {
+            race_time,
+            record_distance,
+        },
+    ))
+}
+
+fn parse_line_as_int<'a>(tag_str: &'a str, input: &'a str) -> IResult<&'a str, u64> {
+    let (input, (_, ints)) = labelled(tag(tag_str), list(digit1))(input)?;
+
+    Ok((input, ints.concat().parse().unwrap()))
+}
@@ -0,0 +1,255 @@
+use crate::{
+    graph::{brent, Cycle, Interner, NodeId},
+    math::first_common_time,
+    parallel,
+};
+use nom::{
+    bytes::complete::tag,
+    character::complete::{alphanumeric1, anychar, newline},
+    combinator::map_res,
+    multi::{count, many1, separated_list0},
+    IResult,
+};
+
+crate::day!(8);
+
+pub fn part_1(input: &str) -> Option<u64> {
+    let (input, (instructions, network)) = parse_input(input).unwrap();
+
+    assert_eq!(input, "");
+
+    let start = network.nodes.get(&"AAA").unwrap();
+
+    Some(count_steps(&instructions, &network, start, |n| n == "ZZZ"))
+}
+
+pub fn part_2(input: &str) -> Option<u64> {
+    let (input, (instructions, network)) = parse_input(input).unwrap();
+
+    assert_eq!(input, "");
+
+    // Find all the starting node ids
+    let starts: Vec<_> = network
+        .nodes
+        .keys()
+        .iter()
+        .enumerate()
+        .filter(|(_, k)| k.ends_with('A'))
+        .map(|(id, _)| NodeId(id))
+        .collect();
+
+    // Find where each ghost's walk starts repeating and when it is on an end node
+    let ghosts = parallel::map(starts, |start| {
+        GhostPath::new(&instructions, &network, start)
+    });
+
+    first_meeting(&ghosts)
+}
+
+/// Checks that the input is a valid map, returning how many nodes it has
+pub fn parse(input: &str) -> Result<usize, String> {
+    match parse_input(input) {
+        Ok(("", (_, network))) => Ok(network.edges.len()),
+        _ => Err("expected a list of instructions followed by the network".to_string()),
+    }
+}
+
+/// Finds the first step at which every ghost is on an end node at once
+fn first_meeting(ghosts: &[GhostPath]) -> Option<u64> {
+    // A meeting before some ghost has entered its cycle has to be one of that
+    // ghost's tail hits, and is earlier than any meeting inside the cycles
+    let tail_meeting = ghosts
+        .iter()
+        .flat_map(|g| &g.tail_hits)
+        .filter(|time| ghosts.iter().all(|g| g.is_at_end(**time)))
+        .min();
+
+    if let Some(time) = tail_meeting {
+        return Some(*time);
+    }
+
+    // Otherwise every ghost is in its cycle, so try each combination of the
+    // hits within the cycles. The cycles are not assumed to line up, so
+    // this uses the generalised CRT rather than a plain LCM.
+    let mut combinations = vec![Vec::new()];
+
+    for ghost in ghosts {
+        combinations = combinations
+            .into_iter()
+            .flat_map(|events: Vec<(u64, u64)>| {
+                ghost.cycle_hits.iter().map(move |hit| {
+                    let mut events = events.clone();
+                    events.push((*hit, ghost.cycle.length as u64));
+                    events
+                })
+            })
+            .collect();
+    }
+
+    combinations
+        .iter()
+        .filter_map(|events| first_common_time(events))
+        .filter_map(|time| time.try_into().ok())
+        .min()
+}
+
+/// The steps at which a ghost stands on an end node. The walk only depends on
+/// the current node and the position in the instruction list, so it must
+/// eventually repeat.
+pub struct GhostPath {
+    /// End node visits before the walk starts repeating
+    tail_hits: Vec<u64>,
+    /// End node visits during the first repetition of the cycle
+    cycle_hits: Vec<u64>,
+    cycle: Cycle,
+}
+
+impl GhostPath {
+    pub fn new(instructions: &InstructionList, network: &Network, start: NodeId) -> Self {
+        let step = |&(node, i): &(NodeId, usize)| {
+            (
+                network.step(node, &instructions[i]),
+                (i + 1) % instructions.len(),
+            )
+        };
+
+        let cycle = brent((start, 0), step);
+
+        let mut tail_hits = Vec::new();
+        let mut cycle_hits = Vec::new();
+        let mut state = (start, 0);
+
+        for time in 0..cycle.start + cycle.length {
+            if network.nodes.key(state.0).ends_with('Z') {
+                if time < cycle.start {
+                    tail_hits.push(time as u64);
+                } else {
+                    cycle_hits.push(time as u64);
+                }
+            }
+
+            state = step(&state);
+        }
+
+        Self {
+            tail_hits,
+            cycle_hits,
+            cycle,
+        }
+    }
+
+    pub fn is_at_end(&self, time: u64) -> bool {
+        let time = self.cycle.reduce(time as usize) as u64;
+
+        self.tail_hits.contains(&time) || self.cycle_hits.contains(&time)
+    }
+}
+
+pub fn count_steps(
+    instructions: &InstructionList,
+    network: &Network,
+    starting_node: NodeId,
+    end_condition_predicate: fn(&str) -> bool,
+) -> u64 {
+    let mut steps = 0;
+    let mut current_node = starting_node;
+
+    'outer: loop {
+        for instruction in instructions {
+            if end_condition_predicate(network.nodes.key(current_node)) {
+                break 'outer;
+            }
+
+            current_node = network.step(current_node, instruction);
+            steps += 1;
+        }
+    }
+
+    steps
+}
+
+// ================== TYPES ==================
+
+#[derive(Debug)]
+pub enum Instruction {
+    Left,
+    Right,
+}
+
+impl TryFrom<char> for Instruction {
+    type Error = ();
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        let instruction = match value {
+            'L' => Self::Left,
+            'R' => Self::Right,
+            _ => return Err(()),
+        };
+
+        Ok(instruction)
+    }
+}
+
+pub type Node<'a> = (&'a str, (&'a str, &'a str));
+pub type InstructionList = Vec<Instruction>;
+
+/// The nodes of the map, interned so that each step is an index lookup
+/// rather than a hash of the node name
+pub struct Network<'a> {
+    pub nodes: Interner<&'a str>,
+    pub edges: Vec<(NodeId, NodeId)>,
+}
+
+impl<'a> Network<'a> {
+    pub fn new(node_list: Vec<Node<'a>>) -> Self {
+        let mut nodes = Interner::new();
+
+        // Intern in definition order first so that ids line up with `edges`
+        for (id, _) in &node_list {
+            nodes.intern(*id);
+        }
+
+        let edges = node_list
+            .into_iter()
+            .map(|(_, (left, right))| (nodes.intern(left), nodes.intern(right)))
+            .collect();
+
+        Self { nodes, edges }
+    }
+
+    pub fn step(&self, node: NodeId, instruction: &Instruction) -> NodeId {
+        let (left, right) = self.edges[node.0];
+
+        match instruction {
+            Instruction::Left => left,
+            Instruction::Right => right,
+        }
+    }
+}
+
+// ================== PARSING ==================
+
+pub fn parse_input(input: &str) -> IResult<&str, (InstructionList, Network<'_>)> {
+    let (input, instructions) = many1(parse_instruction)(input)?;
+    let (input, _) = count(newline, 2)(input)?;
+    let (input, nodes) = separated_list0(newline, parse_node)(input)?;
+
+    let network = Network::new(nodes);
+
+    Ok((input, (instructions, network)))
+}
+
+fn parse_node(input: &str) -> IResult<&str, Node<'_>> {
+    let (input, node_id) = alphanumeric1(input)?;
+    let (input, _) = tag(" = (")(input)?;
+    let (input, left) = alphanumeric1(input)?;
+    let (input, _) = tag(", ")(input)?;
+    let (input, right) = alphanumeric1(input)?;
+    let (input, _) = tag(")")(input)?;
+
+    Ok((input, (node_id, (left, right))))
+}
+
+fn parse_instruction(input: &str) -> IResult<&str, Instruction> {
+    map_res(anychar, Instruction::try_from)(input)
+}
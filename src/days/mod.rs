@@ -0,0 +1,11 @@
+//! The solutions, one module per day. Each module exposes `part_1` and
+//! `part_2` along with the types and parsers they are built on.
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day07;
+pub mod day08;
@@ -0,0 +1,575 @@
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+use num::Zero;
+
+/// A dense id handed out by an [`Interner`] or [`Graph`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeId(pub usize);
+
+/// Assigns dense [`NodeId`]s to arbitrary keys (such as node names)
+#[derive(Debug, Clone)]
+pub struct Interner<K> {
+    ids: HashMap<K, NodeId>,
+    keys: Vec<K>,
+}
+
+impl<K: Hash + Eq + Clone> Interner<K> {
+    pub fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            keys: Vec::new(),
+        }
+    }
+
+    /// Returns the id for a key, assigning a new one if it has not been seen before
+    pub fn intern(&mut self, key: K) -> NodeId {
+        match self.ids.entry(key) {
+            Entry::Occupied(entry) => *entry.get(),
+            Entry::Vacant(entry) => {
+                let id = NodeId(self.keys.len());
+                self.keys.push(entry.key().clone());
+                entry.insert(id);
+                id
+            }
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<NodeId> {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: NodeId) -> &K {
+        &self.keys[id.0]
+    }
+
+    /// All interned keys, indexed by id
+    pub fn keys(&self) -> &[K] {
+        &self.keys
+    }
+
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for Interner<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A directed graph stored as adjacency lists with edge weights of type `W`
+#[derive(Debug, Clone, Default)]
+pub struct Graph<W = ()> {
+    edges: Vec<Vec<(NodeId, W)>>,
+}
+
+impl<W> Graph<W> {
+    pub fn new() -> Self {
+        Self { edges: Vec::new() }
+    }
+
+    pub fn with_nodes(count: usize) -> Self {
+        Self {
+            edges: std::iter::repeat_with(Vec::new).take(count).collect(),
+        }
+    }
+
+    pub fn add_node(&mut self) -> NodeId {
+        self.edges.push(Vec::new());
+        NodeId(self.edges.len() - 1)
+    }
+
+    /// Adds a directed edge, growing the graph if either node does not exist yet
+    pub fn add_edge(&mut self, from: NodeId, to: NodeId, weight: W) {
+        let required = from.0.max(to.0) + 1;
+
+        if self.edges.len() < required {
+            self.edges.resize_with(required, Vec::new);
+        }
+
+        self.edges[from.0].push((to, weight));
+    }
+
+    /// Adds an edge in both directions
+    pub fn add_undirected_edge(&mut self, a: NodeId, b: NodeId, weight: W)
+    where
+        W: Clone,
+    {
+        self.add_edge(a, b, weight.clone());
+        self.add_edge(b, a, weight);
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = NodeId> {
+        (0..self.edges.len()).map(NodeId)
+    }
+
+    pub fn edges(&self, node: NodeId) -> &[(NodeId, W)] {
+        &self.edges[node.0]
+    }
+
+    pub fn neighbours(&self, node: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.edges[node.0].iter().map(|(to, _)| *to)
+    }
+}
+
+/// Finds the shortest number of steps from `start` to every reachable state
+pub fn bfs<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[&node];
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = distances.entry(next.clone()) {
+                entry.insert(distance + 1);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds a shortest path (by number of steps) from `start` to any state
+/// matching `is_goal`, including both ends
+pub fn bfs_path<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Vec<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(reconstruct_path(&parents, node));
+        }
+
+        for next in successors(&node) {
+            if let Entry::Vacant(entry) = parents.entry(next.clone()) {
+                entry.insert(Some(node.clone()));
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the lowest total cost from `start` to every reachable state
+pub fn dijkstra<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Hash + Eq + Clone + Ord,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::zero())]);
+    let mut heap = BinaryHeap::from([Reverse((C::zero(), start))]);
+
+    while let Some(Reverse((cost, node))) = heap.pop() {
+        // Skip stale heap entries that have since been improved upon
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                heap.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+
+    costs
+}
+
+/// Finds a lowest cost path from `start` to any state matching `is_goal`,
+/// returning the path (including both ends) and its cost
+pub fn dijkstra_path<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Hash + Eq + Clone + Ord,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+/// A* search from `start` to any state matching `is_goal`. The `heuristic`
+/// must never overestimate the remaining cost for the result to be optimal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<(Vec<N>, C)>
+where
+    N: Hash + Eq + Clone + Ord,
+    C: Copy + Ord + Zero + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs = HashMap::from([(start.clone(), C::zero())]);
+    let mut parents: HashMap<N, Option<N>> = HashMap::from([(start.clone(), None)]);
+    let mut heap = BinaryHeap::from([Reverse((heuristic(&start), C::zero(), start))]);
+
+    while let Some(Reverse((_, cost, node))) = heap.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some((reconstruct_path(&parents, node), cost));
+        }
+
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), Some(node.clone()));
+                heap.push(Reverse((next_cost + heuristic(&next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+fn reconstruct_path<N: Hash + Eq + Clone>(parents: &HashMap<N, Option<N>>, end: N) -> Vec<N> {
+    let mut path = vec![end];
+
+    while let Some(Some(parent)) = parents.get(path.last().unwrap()) {
+        path.push(parent.clone());
+    }
+
+    path.reverse();
+    path
+}
+
+/// The shape of the sequence `x0, f(x0), f(f(x0)), ...` which eventually repeats
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle {
+    /// Index of the first state that is part of the cycle
+    pub start: usize,
+    /// Number of states in the cycle
+    pub length: usize,
+}
+
+impl Cycle {
+    /// Maps a (possibly huge) step index onto the earliest step that reaches the same state
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle in the sequence generated by repeatedly applying `f` to
+/// `x0` using Brent's algorithm, which stores only a constant number of states
+pub fn brent<N: PartialEq + Clone>(x0: N, mut f: impl FnMut(&N) -> N) -> Cycle {
+    // Find the cycle length by searching successive powers of two
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = x0.clone();
+    let mut hare = f(&x0);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+
+        hare = f(&hare);
+        length += 1;
+    }
+
+    // Find the start of the cycle with the hare `length` steps ahead
+    let mut tortoise = x0.clone();
+    let mut hare = x0;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+
+    let mut start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Finds the cycle in the sequence generated by repeatedly applying `f` to
+/// `x0` using Floyd's tortoise and hare algorithm
+pub fn floyd<N: PartialEq + Clone>(x0: N, mut f: impl FnMut(&N) -> N) -> Cycle {
+    let mut tortoise = f(&x0);
+    let mut hare = f(&tortoise);
+
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut start = 0;
+    let mut tortoise = x0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        start += 1;
+    }
+
+    let mut length = 1;
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle { start, length }
+}
+
+/// Returned by [`topological_sort`] when the graph is not acyclic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError<N>(pub Vec<N>);
+
+/// Orders `nodes` so that every node comes before its successors using Kahn's
+/// algorithm. On failure, returns the nodes that are part of (or downstream
+/// of) a cycle.
+pub fn topological_sort<N, I>(
+    nodes: &[N],
+    mut successors: impl FnMut(&N) -> I,
+) -> Result<Vec<N>, CycleError<N>>
+where
+    N: Hash + Eq + Clone,
+    I: IntoIterator<Item = N>,
+{
+    let mut in_degree: HashMap<N, usize> = nodes.iter().map(|n| (n.clone(), 0)).collect();
+    let mut edges: HashMap<N, Vec<N>> = HashMap::new();
+
+    for node in nodes {
+        for next in successors(node) {
+            *in_degree.entry(next.clone()).or_insert(0) += 1;
+            edges.entry(node.clone()).or_default().push(next);
+        }
+    }
+
+    let mut queue: VecDeque<N> = nodes
+        .iter()
+        .filter(|n| in_degree[*n] == 0)
+        .cloned()
+        .collect();
+    let mut sorted = Vec::with_capacity(in_degree.len());
+
+    while let Some(node) = queue.pop_front() {
+        for next in edges.get(&node).into_iter().flatten() {
+            let degree = in_degree.get_mut(next).unwrap();
+            *degree -= 1;
+
+            if *degree == 0 {
+                queue.push_back(next.clone());
+            }
+        }
+
+        sorted.push(node);
+    }
+
+    if sorted.len() < in_degree.len() {
+        let remaining = in_degree
+            .into_iter()
+            .filter(|(_, degree)| *degree > 0)
+            .map(|(n, _)| n)
+            .collect();
+
+        return Err(CycleError(remaining));
+    }
+
+    Ok(sorted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn interner_assigns_dense_ids() {
+        let mut interner = Interner::new();
+
+        assert_eq!(interner.intern("AAA"), NodeId(0));
+        assert_eq!(interner.intern("BBB"), NodeId(1));
+        assert_eq!(interner.intern("AAA"), NodeId(0));
+        assert_eq!(interner.get(&"BBB"), Some(NodeId(1)));
+        assert_eq!(interner.get(&"ZZZ"), None);
+        assert_eq!(*interner.key(NodeId(1)), "BBB");
+        assert_eq!(interner.keys(), &["AAA", "BBB"]);
+    }
+
+    fn example_graph() -> Graph<u32> {
+        //   0 --1--> 1 --1--> 2
+        //   |                 ^
+        //   +-------5---------+
+        //   3 (disconnected)
+        let mut graph = Graph::with_nodes(4);
+        graph.add_edge(NodeId(0), NodeId(1), 1);
+        graph.add_edge(NodeId(1), NodeId(2), 1);
+        graph.add_edge(NodeId(0), NodeId(2), 5);
+        graph
+    }
+
+    #[test]
+    fn adjacency_storage() {
+        let mut graph = example_graph();
+
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(
+            graph.neighbours(NodeId(0)).collect::<Vec<_>>(),
+            vec![NodeId(1), NodeId(2)]
+        );
+
+        graph.add_undirected_edge(NodeId(3), NodeId(5), 2);
+        assert_eq!(graph.node_count(), 6);
+        assert_eq!(graph.edges(NodeId(5)), &[(NodeId(3), 2)]);
+    }
+
+    #[test]
+    fn bfs_counts_steps() {
+        let graph = example_graph();
+        let distances = bfs(NodeId(0), |n| graph.neighbours(*n).collect::<Vec<_>>());
+
+        assert_eq!(distances[&NodeId(1)], 1);
+        assert_eq!(distances[&NodeId(2)], 1);
+        assert!(!distances.contains_key(&NodeId(3)));
+
+        let path = bfs_path(0u32, |n| [n + 3, n + 5], |n| *n == 11);
+        assert_eq!(path, Some(vec![0, 3, 6, 11]));
+        assert_eq!(bfs_path(0u32, |_| [], |n| *n == 1), None);
+    }
+
+    #[test]
+    fn dijkstra_finds_lowest_cost() {
+        let graph = example_graph();
+        let edges = |n: &NodeId| graph.edges(*n).to_vec();
+
+        let costs = dijkstra(NodeId(0), edges);
+        assert_eq!(costs[&NodeId(2)], 2);
+
+        let (path, cost) = dijkstra_path(NodeId(0), edges, |n| *n == NodeId(2)).unwrap();
+        assert_eq!(path, vec![NodeId(0), NodeId(1), NodeId(2)]);
+        assert_eq!(cost, 2);
+
+        assert_eq!(dijkstra_path(NodeId(0), edges, |n| *n == NodeId(3)), None);
+    }
+
+    #[test]
+    fn astar_on_a_grid() {
+        // Walk a 10x10 open grid from the top left to the bottom right corner
+        let successors = |&(x, y): &(i32, i32)| {
+            [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                .into_iter()
+                .filter(|(x, y)| (0..10).contains(x) && (0..10).contains(y))
+                .map(|p| (p, 1))
+                .collect::<Vec<_>>()
+        };
+        let heuristic = |&(x, y): &(i32, i32)| (9 - x) + (9 - y);
+
+        let (path, cost) = astar((0, 0), successors, heuristic, |p| *p == (9, 9)).unwrap();
+
+        assert_eq!(cost, 18);
+        assert_eq!(path.len(), 19);
+        assert_eq!(path.first(), Some(&(0, 0)));
+        assert_eq!(path.last(), Some(&(9, 9)));
+    }
+
+    #[test]
+    fn cycle_detection() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 5 -> 2 ...
+        let f = |x: &u32| if *x == 5 { 2 } else { x + 1 };
+        let expected = Cycle {
+            start: 2,
+            length: 4,
+        };
+
+        assert_eq!(brent(0, f), expected);
+        assert_eq!(floyd(0, f), expected);
+
+        // A fixed point is a cycle of length one
+        assert_eq!(
+            brent(7, |x| *x),
+            Cycle {
+                start: 0,
+                length: 1
+            }
+        );
+
+        // Skipping ahead with the cycle gives the same state as stepping
+        let mut state = 0;
+        for _ in 0..1000 {
+            state = f(&state);
+        }
+        let mut reduced = 0;
+        for _ in 0..expected.reduce(1000) {
+            reduced = f(&reduced);
+        }
+        assert_eq!(state, reduced);
+
+        // A linear congruential generator has a long tail and cycle
+        let lcg = |x: &u64| (x * x + 1) % 255;
+        assert_eq!(brent(3, lcg), floyd(3, lcg));
+    }
+
+    #[test]
+    fn topological_order() {
+        let edges: HashMap<char, Vec<char>> = HashMap::from([
+            ('a', vec!['b', 'c']),
+            ('b', vec!['d']),
+            ('c', vec!['d']),
+            ('d', vec![]),
+        ]);
+        let successors = |n: &char| edges[n].clone();
+
+        let sorted = topological_sort(&['d', 'c', 'b', 'a'], successors).unwrap();
+        assert_eq!(sorted, vec!['a', 'b', 'c', 'd']);
+
+        let cyclic = |n: &char| match n {
+            'a' => vec!['b'],
+            'b' => vec!['c'],
+            'c' => vec!['b'],
+            _ => vec![],
+        };
+        let CycleError(mut remaining) = topological_sort(&['a', 'b', 'c'], cyclic).unwrap_err();
+        remaining.sort();
+        assert_eq!(remaining, vec!['b', 'c']);
+    }
+}
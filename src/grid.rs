@@ -0,0 +1,561 @@
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
+
+/// A dense 2D grid stored in row-major order
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridError {
+    /// A row did not have the same length as the first row
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row {} has {} cells but the grid is {} cells wide",
+                row, found, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `fill`
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Creates a grid from a list of rows which must all be the same length
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Result<Self, GridError> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, Vec::len);
+        let mut cells = Vec::with_capacity(width * height);
+
+        for (row, cells_in_row) in rows.into_iter().enumerate() {
+            if cells_in_row.len() != width {
+                return Err(GridError::Ragged {
+                    row,
+                    expected: width,
+                    found: cells_in_row.len(),
+                });
+            }
+
+            cells.extend(cells_in_row);
+        }
+
+        Ok(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Creates a grid from a list of rows, padding short rows with `fill` so
+    /// that the grid is as wide as the longest row
+    pub fn from_rows_padded(rows: Vec<Vec<T>>, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        let height = rows.len();
+        let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+        let mut cells = Vec::with_capacity(width * height);
+
+        for row in rows {
+            let padding = width - row.len();
+            cells.extend(row);
+            cells.extend(std::iter::repeat_n(fill.clone(), padding));
+        }
+
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    /// Parses a rectangular grid from text, converting each character with `cell`
+    pub fn parse(input: &str, cell: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        Self::from_rows(split_rows(input, cell))
+    }
+
+    /// Parses a possibly ragged grid from text, padding short lines with `fill`
+    pub fn parse_padded(input: &str, fill: T, cell: impl FnMut(char) -> T) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_rows_padded(split_rows(input, cell), fill)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, pos: Position) -> bool {
+        pos.row < self.height && pos.col < self.width
+    }
+
+    pub fn get(&self, pos: Position) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.row * self.width + pos.col])
+    }
+
+    pub fn get_mut(&mut self, pos: Position) -> Option<&mut T> {
+        if !self.contains(pos) {
+            return None;
+        }
+
+        Some(&mut self.cells[pos.row * self.width + pos.col])
+    }
+
+    /// Returns the cell one step away from `pos` in the given direction, if
+    /// that cell is inside the grid
+    pub fn step(&self, pos: Position, direction: Direction) -> Option<Position> {
+        pos.step(direction).filter(|p| self.contains(*p))
+    }
+
+    pub fn row(&self, row: usize) -> Option<&[T]> {
+        (row < self.height).then(|| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which happens for zero-width grids
+        (0..self.height).map(|row| &self.cells[row * self.width..(row + 1) * self.width])
+    }
+
+    pub fn column(&self, col: usize) -> impl Iterator<Item = &T> {
+        let height = if col < self.width { self.height } else { 0 };
+
+        (0..height).map(move |row| &self.cells[row * self.width + col])
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|col| self.column(col))
+    }
+
+    /// Iterates over every position in row-major order
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+
+        (0..self.height).flat_map(move |row| (0..width).map(move |col| Position::new(row, col)))
+    }
+
+    /// Iterates over every cell along with its position in row-major order
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// The in-bounds orthogonal neighbours of `pos`
+    pub fn neighbours_4(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// The in-bounds orthogonal and diagonal neighbours of `pos`
+    pub fn neighbours_8(&self, pos: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(pos, d))
+    }
+
+    /// Finds every maximal horizontal run of cells matching `predicate`, in
+    /// row-major order. Runs never wrap onto the next row.
+    pub fn spans(&self, mut predicate: impl FnMut(&T) -> bool) -> Vec<Span> {
+        let mut spans = Vec::new();
+
+        for (row, cells) in self.rows().enumerate() {
+            let mut start = None;
+
+            for (col, cell) in cells.iter().enumerate() {
+                match (predicate(cell), start) {
+                    (true, None) => start = Some(col),
+                    (false, Some(s)) => {
+                        spans.push(Span::new(row, s, col - s));
+                        start = None;
+                    }
+                    _ => {}
+                }
+            }
+
+            if let Some(s) = start {
+                spans.push(Span::new(row, s, self.width - s));
+            }
+        }
+
+        spans
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// Renders the grid as text, one line per row
+    pub fn render(&self, mut cell: impl FnMut(&T) -> char) -> String {
+        let mut output = String::with_capacity(self.height * (self.width + 1));
+
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                output.push('\n');
+            }
+
+            output.extend(row.iter().map(&mut cell));
+        }
+
+        output
+    }
+}
+
+fn split_rows<T>(input: &str, mut cell: impl FnMut(char) -> T) -> Vec<Vec<T>> {
+    input
+        .lines()
+        .map(|line| line.chars().map(&mut cell).collect())
+        .collect()
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Position) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, pos: Position) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("{:?} is outside of the grid", pos))
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render(|c| *c))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position {
+    pub row: usize,
+    pub col: usize,
+}
+
+impl Position {
+    pub fn new(row: usize, col: usize) -> Self {
+        Self { row, col }
+    }
+
+    /// Moves one step in the given direction, returning `None` if that would
+    /// move above the first row or left of the first column
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let (d_row, d_col) = direction.delta();
+
+        Some(Self {
+            row: self.row.checked_add_signed(d_row)?,
+            col: self.col.checked_add_signed(d_col)?,
+        })
+    }
+
+    pub fn manhattan_distance(self, other: Self) -> usize {
+        self.row.abs_diff(other.row) + self.col.abs_diff(other.col)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four orthogonal directions in clockwise order
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions in clockwise order
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The `(row, col)` offset of one step in this direction
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::North => (-1, 0),
+            Direction::NorthEast => (-1, 1),
+            Direction::East => (0, 1),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (1, 0),
+            Direction::SouthWest => (1, -1),
+            Direction::West => (0, -1),
+            Direction::NorthWest => (-1, -1),
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        self.rotate(4)
+    }
+
+    /// Turns 90 degrees clockwise
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 90 degrees counter-clockwise
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    fn rotate(self, eighths: usize) -> Self {
+        let index = Self::ALL.iter().position(|d| *d == self).unwrap();
+        Self::ALL[(index + eighths) % 8]
+    }
+}
+
+/// A horizontal run of cells within a single row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Span {
+    pub row: usize,
+    pub start: usize,
+    pub len: usize,
+}
+
+impl Span {
+    pub fn new(row: usize, start: usize, len: usize) -> Self {
+        Self { row, start, len }
+    }
+
+    /// The column just past the last cell of the span
+    pub fn end(&self) -> usize {
+        self.start + self.len
+    }
+
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let row = self.row;
+        (self.start..self.end()).map(move |col| Position::new(row, col))
+    }
+
+    /// Checks if `pos` touches the span orthogonally or diagonally (but is not part of it)
+    pub fn is_adjacent(&self, pos: Position) -> bool {
+        let in_span = pos.row == self.row && (self.start..self.end()).contains(&pos.col);
+
+        !in_span
+            && pos.row.abs_diff(self.row) <= 1
+            && pos.col + 1 >= self.start
+            && pos.col <= self.end()
+    }
+
+    /// The in-bounds cells surrounding the span
+    pub fn neighbours<'a, T>(&self, grid: &'a Grid<T>) -> impl Iterator<Item = Position> + 'a {
+        let span = *self;
+
+        let min_row = span.row.saturating_sub(1);
+        let max_row = (span.row + 1).min(grid.height().saturating_sub(1));
+        let min_col = span.start.saturating_sub(1);
+        let max_col = span.end().min(grid.width().saturating_sub(1));
+
+        (min_row..=max_row)
+            .flat_map(move |row| (min_col..=max_col).map(move |col| Position::new(row, col)))
+            .filter(move |pos| grid.contains(*pos) && span.is_adjacent(*pos))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = "467..114..\n...*......\n..35..633.";
+
+    #[test]
+    fn parses_rectangular_text() {
+        let grid = Grid::parse(EXAMPLE, |c| c).unwrap();
+
+        assert_eq!(grid.width(), 10);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Position::new(1, 3)], '*');
+        assert_eq!(grid.get(Position::new(3, 0)), None);
+        assert_eq!(grid.get(Position::new(0, 10)), None);
+        assert_eq!(grid.to_string(), EXAMPLE);
+    }
+
+    #[test]
+    fn rejects_or_pads_ragged_text() {
+        assert_eq!(
+            Grid::parse("ab\nc", |c| c),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+
+        let grid = Grid::parse_padded("ab\nc\n", '.', |c| c);
+        assert_eq!(grid.to_string(), "ab\nc.");
+    }
+
+    #[test]
+    fn handles_empty_grids() {
+        let grid = Grid::parse("", |c| c).unwrap();
+
+        assert!(grid.is_empty());
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.spans(|_| true), vec![]);
+
+        let grid: Grid<char> = Grid::from_rows(vec![vec![], vec![]]).unwrap();
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.columns().count(), 0);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = Grid::parse("abc\ndef", |c| c).unwrap();
+
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<String>(), "be");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.copied().collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(
+            grid.iter().map(|(p, c)| (p.row, p.col, *c)).last(),
+            Some((1, 2, 'f'))
+        );
+    }
+
+    #[test]
+    fn neighbourhoods_are_bounds_checked() {
+        let grid = Grid::new(3, 2, 0);
+
+        let corner: Vec<_> = grid.neighbours_4(Position::new(0, 0)).collect();
+        assert_eq!(corner, vec![Position::new(0, 1), Position::new(1, 0)]);
+
+        assert_eq!(grid.neighbours_8(Position::new(0, 0)).count(), 3);
+        assert_eq!(grid.neighbours_8(Position::new(1, 1)).count(), 5);
+        assert_eq!(grid.neighbours_4(Position::new(1, 2)).count(), 2);
+    }
+
+    #[test]
+    fn directions_rotate() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.opposite(), Direction::SouthWest);
+        assert_eq!(Position::new(0, 0).step(Direction::North), None);
+        assert_eq!(
+            Position::new(1, 1).step(Direction::SouthWest),
+            Some(Position::new(2, 0))
+        );
+    }
+
+    #[test]
+    fn extracts_spans() {
+        let grid = Grid::parse(EXAMPLE, |c| c).unwrap();
+        let spans = grid.spans(char::is_ascii_digit);
+
+        assert_eq!(
+            spans,
+            vec![
+                Span::new(0, 0, 3),
+                Span::new(0, 5, 3),
+                Span::new(2, 2, 2),
+                Span::new(2, 6, 3),
+            ]
+        );
+
+        // Runs that reach the end of a row are closed at the row boundary
+        let grid = Grid::parse(".11\n11.", |c| c).unwrap();
+        assert_eq!(
+            grid.spans(|c| *c == '1'),
+            vec![Span::new(0, 1, 2), Span::new(1, 0, 2)]
+        );
+    }
+
+    #[test]
+    fn span_adjacency() {
+        let grid = Grid::parse(EXAMPLE, |c| c).unwrap();
+        let star = Position::new(1, 3);
+
+        let adjacent: Vec<_> = grid
+            .spans(char::is_ascii_digit)
+            .into_iter()
+            .filter(|s| s.is_adjacent(star))
+            .collect();
+
+        assert_eq!(adjacent, vec![Span::new(0, 0, 3), Span::new(2, 2, 2)]);
+
+        let span = Span::new(0, 0, 3);
+        assert!(!span.is_adjacent(Position::new(0, 1)));
+        assert_eq!(span.neighbours(&grid).count(), 5);
+        assert!(span.neighbours(&grid).any(|p| grid[p] == '*'));
+    }
+
+    #[test]
+    fn renders_with_custom_cells() {
+        let grid = Grid::parse("#.\n.#", |c| c == '#').unwrap();
+
+        assert_eq!(grid.render(|b| if *b { 'X' } else { ' ' }), "X \n X");
+        assert_eq!(grid.map(|b| *b as u8).row(0), Some(&[1, 0][..]));
+    }
+}
@@ -0,0 +1,247 @@
+use std::{fmt, fmt::Debug, fs, io, path::PathBuf, str::FromStr};
+
+use crate::{
+    crypto::{CryptoError, InputKey},
+    manifest::{self, Manifest, Verification},
+    normalize::Normalize,
+    Part,
+};
+
+/// Inputs and examples compiled into the binary by the `embed` feature (see `build.rs`)
+#[cfg(feature = "embed")]
+mod embedded {
+    include!(concat!(env!("OUT_DIR"), "/embedded.rs"));
+}
+
+#[cfg(feature = "embed")]
+fn embedded_input(day: u32) -> Option<&'static str> {
+    embedded::INPUTS
+        .iter()
+        .find(|(d, _)| *d == day)
+        .map(|(_, input)| *input)
+}
+
+#[cfg(not(feature = "embed"))]
+fn embedded_input(_day: u32) -> Option<&'static str> {
+    None
+}
+
+#[cfg(feature = "embed")]
+fn embedded_example(day: u32, part: Part) -> Option<&'static str> {
+    embedded::EXAMPLES
+        .iter()
+        .find(|(d, p, _)| *d == day && *p == part as u8)
+        .map(|(_, _, example)| *example)
+}
+
+#[cfg(not(feature = "embed"))]
+fn embedded_example(_day: u32, _part: Part) -> Option<&'static str> {
+    None
+}
+
+pub fn input_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("data/inputs/{:02}.txt", day))
+}
+
+/// Where the encrypted copy of an input is stored (see [`crate::crypto`])
+pub fn encrypted_input_path(day: u32) -> PathBuf {
+    PathBuf::from(format!("data/inputs/{:02}.txt.enc", day))
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    /// Neither the plain nor the encrypted input exists
+    NotFound {
+        day: u32,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// Only an encrypted input exists, but no key is available to decrypt it
+    MissingKey {
+        path: PathBuf,
+    },
+    Crypto {
+        path: PathBuf,
+        source: CryptoError,
+    },
+    /// An example file is missing the `---` line separating the answer from the input
+    InvalidExample {
+        path: PathBuf,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::NotFound { day } => {
+                write!(f, "Input file not found: {}", input_path(*day).display())?;
+
+                if cfg!(feature = "embed") {
+                    write!(f, " (and it was not embedded at build time)")?;
+                }
+
+                Ok(())
+            }
+            InputError::Io { path, source } => {
+                write!(f, "Failed to read {}: {}", path.display(), source)
+            }
+            InputError::MissingKey { path } => write!(
+                f,
+                "{} is encrypted but no key was found (set {} or create {})",
+                path.display(),
+                crate::crypto::KEY_ENV_VAR,
+                crate::crypto::KEY_FILE
+            ),
+            InputError::Crypto { path, source } => {
+                write!(f, "Failed to decrypt {}: {}", path.display(), source)
+            }
+            InputError::InvalidExample { path } => {
+                write!(
+                    f,
+                    "Example file is not in the correct format: {}",
+                    path.display()
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn read_input_file(day: u32) -> String {
+    load_input_file(day).unwrap_or_else(|e| panic!("{}", e))
+}
+
+/// Reads the input file for a day, falling back to decrypting the encrypted
+/// copy when the plain file has not been downloaded. Prints a warning if the
+/// input does not match the manifest or looks like an error page.
+pub fn load_input_file(day: u32) -> Result<String, InputError> {
+    load_input_file_with(day, Normalize::default())
+}
+
+/// Like [`load_input_file`], with a day-specific normalisation policy
+pub fn load_input_file_with(day: u32, normalize: Normalize) -> Result<String, InputError> {
+    let file = load_raw_input_file(day)?;
+
+    for warning in integrity_warnings(day, &file) {
+        eprintln!("Warning: {}", warning);
+    }
+
+    Ok(normalize.apply(&file))
+}
+
+/// Reads the input for a day exactly as it was downloaded, without trimming.
+/// Inputs embedded with the `embed` feature take precedence over the files.
+pub fn load_raw_input_file(day: u32) -> Result<String, InputError> {
+    if let Some(input) = embedded_input(day) {
+        return Ok(input.to_string());
+    }
+
+    let path = input_path(day);
+
+    match fs::read_to_string(&path) {
+        Ok(file) => Ok(file),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => read_encrypted_input_file(day),
+        Err(source) => Err(InputError::Io { path, source }),
+    }
+}
+
+/// Checks an untrimmed input against the manifest and for signs of a failed
+/// download. Inputs that were never recorded in the manifest are not an error.
+pub fn integrity_warnings(day: u32, content: &str) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    match Manifest::load() {
+        Ok(manifest) => {
+            if let mismatch @ Verification::Mismatch { .. } = manifest.verify(day, content) {
+                warnings.push(format!("day {:02} input: {}", day, mismatch));
+            }
+        }
+        Err(e) => warnings.push(format!(
+            "could not read {}: {}",
+            manifest::manifest_path().display(),
+            e
+        )),
+    }
+
+    if let Some(reason) = manifest::suspicious_content(content) {
+        warnings.push(format!("day {:02} {}", day, reason));
+    }
+
+    warnings
+}
+
+fn read_encrypted_input_file(day: u32) -> Result<String, InputError> {
+    let path = encrypted_input_path(day);
+
+    let data = match fs::read(&path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Err(InputError::NotFound { day }),
+        Err(source) => return Err(InputError::Io { path, source }),
+    };
+
+    let key = match InputKey::load() {
+        Ok(Some(key)) => key,
+        Ok(None) => return Err(InputError::MissingKey { path }),
+        Err(source) => return Err(InputError::Crypto { path, source }),
+    };
+
+    key.decrypt(day, &data)
+        .map_err(|source| InputError::Crypto { path, source })
+}
+
+pub fn example_path(day: u32, part: Part) -> PathBuf {
+    PathBuf::from(format!(
+        "data/examples/{:02}/part-{:01}.txt",
+        day, part as u8
+    ))
+}
+
+pub fn read_example_file<T: FromStr>(day: u32, part: Part) -> (T, String)
+where
+    <T as FromStr>::Err: Debug,
+{
+    read_example_file_with(day, part, Normalize::default())
+}
+
+pub fn read_example_file_with<T: FromStr>(day: u32, part: Part, normalize: Normalize) -> (T, String)
+where
+    <T as FromStr>::Err: Debug,
+{
+    let (expected_result, input) =
+        load_example_file(day, part, normalize).unwrap_or_else(|e| match e {
+            InputError::Io { path, .. } => panic!("Example file not found: {}", path.display()),
+            e => panic!("{}", e),
+        });
+
+    let expected_result = expected_result
+        .parse::<T>()
+        .expect("Expected result is not a number");
+
+    (expected_result, input)
+}
+
+/// Reads an example file, returning the expected answer and the input
+pub fn load_example_file(
+    day: u32,
+    part: Part,
+    normalize: Normalize,
+) -> Result<(String, String), InputError> {
+    let path = example_path(day, part);
+    let file = match embedded_example(day, part) {
+        Some(example) => example.to_string(),
+        None => match fs::read_to_string(&path) {
+            Ok(file) => file,
+            Err(source) => return Err(InputError::Io { path, source }),
+        },
+    };
+
+    let file = file.replace("\r\n", "\n");
+    let Some((expected_result, input)) = file.split_once("\n---\n") else {
+        return Err(InputError::InvalidExample { path });
+    };
+
+    Ok((expected_result.trim().to_string(), normalize.apply(input)))
+}
@@ -0,0 +1,461 @@
+use std::ops::Range;
+
+use num::PrimInt;
+
+/// A set of integers stored as sorted, disjoint, non-adjacent half-open ranges
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: PrimInt> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self { ranges: Vec::new() }
+    }
+
+    /// Adds a range to the set, merging it with any ranges it overlaps or touches
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // Find the first range that ends at or after the new range starts (and so might merge)
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        // Find the first range that starts after the new range ends (and so cannot merge)
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= value);
+
+        self.ranges.get(index).is_some_and(|r| r.contains(&value))
+    }
+
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value in the set
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The largest value in the set
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|r| r.end - T::one())
+    }
+
+    /// The total number of values covered by the set
+    pub fn total_len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::zero(), |acc, r| acc + (r.end - r.start))
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+
+        for range in &other.ranges {
+            result.insert(range.clone());
+        }
+
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let a = &self.ranges[i];
+            let b = &other.ranges[j];
+
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+
+            if start < end {
+                ranges.push(start..end);
+            }
+
+            // Advance whichever range finishes first
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // Skip the ranges of `other` which end before this range starts
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                let hole = &other.ranges[k];
+
+                if hole.start > start {
+                    ranges.push(start..hole.start);
+                }
+
+                start = start.max(hole.end);
+                k += 1;
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T: PrimInt> FromIterator<Range<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+
+        for range in iter {
+            set.insert(range);
+        }
+
+        set
+    }
+}
+
+impl<T: PrimInt> From<Range<T>> for IntervalSet<T> {
+    fn from(range: Range<T>) -> Self {
+        std::iter::once(range).collect()
+    }
+}
+
+/// One piece of an [`IntervalMap`], translating `src` so that it starts at `dest_start`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Piece<T> {
+    pub src: Range<T>,
+    pub dest_start: T,
+}
+
+impl<T: PrimInt> Piece<T> {
+    /// Translates a value inside the source range
+    fn translate(&self, value: T) -> T {
+        self.dest_start + (value - self.src.start)
+    }
+
+    /// Translates a value inside the destination range back to the source range
+    fn untranslate(&self, value: T) -> T {
+        self.src.start + (value - self.dest_start)
+    }
+
+    fn dest(&self) -> Range<T> {
+        self.dest_start..self.translate(self.src.end)
+    }
+}
+
+/// A piecewise translation of integers. Values that are not covered by any
+/// piece are mapped to themselves.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct IntervalMap<T> {
+    pieces: Vec<Piece<T>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapError;
+
+impl std::fmt::Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("map pieces have overlapping source ranges")
+    }
+}
+
+impl std::error::Error for OverlapError {}
+
+impl<T: PrimInt> IntervalMap<T> {
+    pub fn new() -> Self {
+        Self { pieces: Vec::new() }
+    }
+
+    /// Adds a piece translating `src` so that it starts at `dest_start`.
+    /// Source ranges may not overlap existing pieces.
+    pub fn insert(&mut self, src: Range<T>, dest_start: T) -> Result<(), OverlapError> {
+        if src.is_empty() {
+            return Ok(());
+        }
+
+        let index = self.pieces.partition_point(|p| p.src.start < src.start);
+
+        let overlaps_previous = index > 0 && self.pieces[index - 1].src.end > src.start;
+        let overlaps_next = self
+            .pieces
+            .get(index)
+            .is_some_and(|p| p.src.start < src.end);
+
+        if overlaps_previous || overlaps_next {
+            return Err(OverlapError);
+        }
+
+        self.pieces.insert(index, Piece { src, dest_start });
+
+        Ok(())
+    }
+
+    pub fn pieces(&self) -> &[Piece<T>] {
+        &self.pieces
+    }
+
+    pub fn apply(&self, value: T) -> T {
+        let index = self.pieces.partition_point(|p| p.src.end <= value);
+
+        match self.pieces.get(index) {
+            Some(piece) if piece.src.contains(&value) => piece.translate(value),
+            _ => value,
+        }
+    }
+
+    /// Splits a range along the piece boundaries, returning each part of the
+    /// input alongside the range it maps to, in input order
+    pub fn segments(&self, range: Range<T>) -> Vec<(Range<T>, Range<T>)> {
+        let mut segments = Vec::new();
+        let mut start = range.start;
+
+        let first = self.pieces.partition_point(|p| p.src.end <= start);
+
+        for piece in &self.pieces[first..] {
+            if start >= range.end || piece.src.start >= range.end {
+                break;
+            }
+
+            // Uncovered gap before this piece maps to itself
+            if start < piece.src.start {
+                segments.push((start..piece.src.start, start..piece.src.start));
+                start = piece.src.start;
+            }
+
+            let end = piece.src.end.min(range.end);
+            segments.push((start..end, piece.translate(start)..piece.translate(end)));
+            start = end;
+        }
+
+        if start < range.end {
+            segments.push((start..range.end, start..range.end));
+        }
+
+        segments
+    }
+
+    /// Maps every value in a range, returning the (possibly overlapping) output ranges
+    pub fn apply_range(&self, range: Range<T>) -> Vec<Range<T>> {
+        self.segments(range)
+            .into_iter()
+            .map(|(_, out)| out)
+            .collect()
+    }
+
+    /// Maps every value in a set
+    pub fn apply_set(&self, set: &IntervalSet<T>) -> IntervalSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|r| self.apply_range(r.clone()))
+            .collect()
+    }
+
+    /// Creates a single map equivalent to applying `self` and then `then`
+    pub fn compose(&self, then: &Self) -> Self {
+        let mut pieces = Vec::new();
+
+        // Values covered by `self` are translated and then split by `then`
+        for piece in &self.pieces {
+            for (input, output) in then.segments(piece.dest()) {
+                pieces.push(Piece {
+                    src: piece.untranslate(input.start)..piece.untranslate(input.end),
+                    dest_start: output.start,
+                });
+            }
+        }
+
+        // Values not covered by `self` pass through unchanged into `then`
+        let covered: IntervalSet<T> = self.pieces.iter().map(|p| p.src.clone()).collect();
+
+        for piece in &then.pieces {
+            let uncovered = IntervalSet::from(piece.src.clone()).difference(&covered);
+
+            for range in uncovered.ranges() {
+                pieces.push(Piece {
+                    src: range.clone(),
+                    dest_start: piece.translate(range.start),
+                });
+            }
+        }
+
+        // Identity pieces are redundant
+        pieces.retain(|p| p.src.start != p.dest_start);
+        pieces.sort_by_key(|p| p.src.start);
+
+        Self { pieces }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::single_range_in_vec_init)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[Range<i64>]) -> IntervalSet<i64> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn insert_merges_overlapping_and_adjacent() {
+        let s = set(&[5..10, 0..2, 2..3, 8..12, 20..20, 15..16]);
+
+        assert_eq!(s.ranges(), &[0..3, 5..12, 15..16]);
+        assert_eq!(s.total_len(), 11);
+        assert_eq!(s.min(), Some(0));
+        assert_eq!(s.max(), Some(15));
+
+        assert_eq!(set(&[0..1, 3..4, 6..7, 2..5]).ranges(), &[0..1, 2..5, 6..7]);
+        assert_eq!(set(&[0..1, 3..4, 6..7, -5..50]).ranges(), &[-5..50]);
+    }
+
+    #[test]
+    fn contains() {
+        let s = set(&[0..3, 5..12]);
+
+        assert!(s.contains(0));
+        assert!(s.contains(11));
+        assert!(!s.contains(3));
+        assert!(!s.contains(12));
+        assert!(!s.contains(-1));
+        assert!(!IntervalSet::<i64>::new().contains(0));
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[0..10, 20..30]);
+        let b = set(&[5..25, 28..40]);
+
+        assert_eq!(a.union(&b).ranges(), &[0..40]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 28..30]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..28]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 30..40]);
+        assert_eq!(a.difference(&a), IntervalSet::new());
+        assert_eq!(a.intersection(&IntervalSet::new()), IntervalSet::new());
+
+        // One range split by several holes
+        let holes = set(&[2..3, 4..5, 8..12]);
+        assert_eq!(
+            set(&[0..10]).difference(&holes).ranges(),
+            &[0..2, 3..4, 5..8]
+        );
+    }
+
+    fn day_5_map() -> IntervalMap<i64> {
+        // seed-to-soil map from the day 5 example
+        let mut map = IntervalMap::new();
+        map.insert(98..100, 50).unwrap();
+        map.insert(50..98, 52).unwrap();
+        map
+    }
+
+    #[test]
+    fn rejects_overlapping_pieces() {
+        let mut map = day_5_map();
+
+        assert_eq!(map.insert(90..99, 0), Err(OverlapError));
+        assert_eq!(map.insert(40..51, 0), Err(OverlapError));
+        assert_eq!(map.insert(100..101, 0), Ok(()));
+        assert_eq!(map.insert(40..50, 0), Ok(()));
+    }
+
+    #[test]
+    fn applies_to_values() {
+        let map = day_5_map();
+
+        assert_eq!(map.apply(79), 81);
+        assert_eq!(map.apply(14), 14);
+        assert_eq!(map.apply(55), 57);
+        assert_eq!(map.apply(98), 50);
+        assert_eq!(map.apply(100), 100);
+    }
+
+    #[test]
+    fn applies_to_ranges() {
+        let map = day_5_map();
+
+        assert_eq!(map.apply_range(79..93), vec![81..95]);
+        assert_eq!(map.apply_range(0..10), vec![0..10]);
+        assert_eq!(
+            map.apply_range(40..110),
+            vec![40..50, 52..100, 50..52, 100..110]
+        );
+        assert_eq!(map.apply_range(97..99), vec![99..100, 50..51]);
+        assert_eq!(map.apply_range(5..5), vec![]);
+
+        let seeds = set(&[79..93, 55..68]);
+        assert_eq!(map.apply_set(&seeds).ranges(), &[57..70, 81..95]);
+    }
+
+    #[test]
+    fn composes_maps() {
+        let first = day_5_map();
+
+        let mut second = IntervalMap::new();
+        second.insert(0..60, 1000).unwrap();
+        second.insert(99..200, -1).unwrap();
+
+        let composed = first.compose(&second);
+
+        for value in -10..250 {
+            assert_eq!(
+                composed.apply(value),
+                second.apply(first.apply(value)),
+                "value {}",
+                value
+            );
+        }
+
+        // Identity pieces are dropped
+        let mut shift = IntervalMap::new();
+        shift.insert(0..10, 5).unwrap();
+        let mut unshift = IntervalMap::new();
+        unshift.insert(5..15, 0).unwrap();
+        assert_eq!(
+            shift.compose(&unshift).pieces(),
+            &[Piece {
+                src: 10..15,
+                dest_start: 5
+            }]
+        );
+    }
+
+    #[test]
+    fn works_with_unsigned_integers() {
+        let mut map = IntervalMap::<u64>::new();
+        map.insert(10..20, 0).unwrap();
+
+        assert_eq!(map.apply(15), 5);
+        assert_eq!(map.apply_range(5..15), vec![5..10, 0..5]);
+    }
+}
@@ -0,0 +1,392 @@
+use std::{collections::BTreeMap, fmt::Write};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{client::unlock_time, time};
+
+/// A private leaderboard as exported by
+/// `https://adventofcode.com/2023/leaderboard/private/view/<id>.json`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// Absent for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u32,
+    /// Keyed by day and then part (both as strings)
+    #[serde(default)]
+    pub completion_day_level: BTreeMap<String, BTreeMap<String, Star>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct Star {
+    pub get_star_ts: i64,
+    /// Breaks ties between stars earned in the same second
+    #[serde(default)]
+    pub star_index: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+
+    pub fn star(&self, day: u32, part: u8) -> Option<Star> {
+        self.completion_day_level
+            .get(&day.to_string())?
+            .get(&part.to_string())
+            .copied()
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Everything the `leaderboard` command reports
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Report {
+    pub event: String,
+    /// Sorted by recomputed local score
+    pub members: Vec<MemberReport>,
+    /// Only days on which somebody earned a star
+    pub days: Vec<DayReport>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MemberReport {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub stars: u32,
+    /// The local score recomputed from the star timestamps
+    pub local_score: u32,
+    /// The local score reported by the server
+    pub reported_local_score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayReport {
+    pub day: u32,
+    pub unlocked_at: i64,
+    /// Sorted by stars earned, then by the time of the last star
+    pub results: Vec<DayResult>,
+}
+
+/// A member's result on one day. Times are seconds since the puzzle unlocked.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayResult {
+    pub rank: usize,
+    pub id: u64,
+    pub name: String,
+    pub part_1_secs: Option<i64>,
+    pub part_2_secs: Option<i64>,
+    /// Time between the two stars
+    pub delta_secs: Option<i64>,
+    /// Local score earned on this day
+    pub points: u32,
+}
+
+/// Computes the local score of every member for one part. The first member
+/// to get a star earns one point per member, the second one fewer and so on.
+fn part_points(leaderboard: &Leaderboard, day: u32, part: u8) -> BTreeMap<u64, u32> {
+    let mut finishers: Vec<(Star, u64)> = leaderboard
+        .members
+        .values()
+        .filter_map(|m| Some((m.star(day, part)?, m.id)))
+        .collect();
+
+    finishers.sort_by_key(|(star, id)| (star.get_star_ts, star.star_index, *id));
+
+    let member_count = leaderboard.members.len() as u32;
+    finishers
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, id))| (id, member_count - i as u32))
+        .collect()
+}
+
+pub fn analyze(leaderboard: &Leaderboard) -> Report {
+    let mut scores: BTreeMap<u64, u32> = BTreeMap::new();
+    let mut days = Vec::new();
+
+    for day in 1..=25 {
+        let unlocked_at = unlock_time(day);
+        let points = [1, 2].map(|part| part_points(leaderboard, day, part));
+
+        for (&id, &p) in points.iter().flatten() {
+            *scores.entry(id).or_default() += p;
+        }
+
+        let mut results: Vec<(DayResult, i64)> = leaderboard
+            .members
+            .values()
+            .filter_map(|m| {
+                let part_1 = m.star(day, 1)?;
+                let part_2 = m.star(day, 2);
+                let last_star = part_2.unwrap_or(part_1).get_star_ts;
+
+                let result = DayResult {
+                    rank: 0,
+                    id: m.id,
+                    name: m.display_name(),
+                    part_1_secs: Some(part_1.get_star_ts - unlocked_at),
+                    part_2_secs: part_2.map(|s| s.get_star_ts - unlocked_at),
+                    delta_secs: part_2.map(|s| s.get_star_ts - part_1.get_star_ts),
+                    points: points.iter().filter_map(|p| p.get(&m.id)).sum(),
+                };
+
+                Some((result, last_star))
+            })
+            .collect();
+
+        if results.is_empty() {
+            continue;
+        }
+
+        results.sort_by_key(|(r, last_star)| (r.part_2_secs.is_none(), *last_star, r.id));
+
+        days.push(DayReport {
+            day,
+            unlocked_at,
+            results: results
+                .into_iter()
+                .enumerate()
+                .map(|(i, (result, _))| DayResult {
+                    rank: i + 1,
+                    ..result
+                })
+                .collect(),
+        });
+    }
+
+    let mut members: Vec<MemberReport> = leaderboard
+        .members
+        .values()
+        .map(|m| MemberReport {
+            rank: 0,
+            id: m.id,
+            name: m.display_name(),
+            stars: m.stars,
+            local_score: scores.get(&m.id).copied().unwrap_or_default(),
+            reported_local_score: m.local_score,
+        })
+        .collect();
+
+    members.sort_by(|a, b| {
+        b.local_score
+            .cmp(&a.local_score)
+            .then(b.stars.cmp(&a.stars))
+            .then(a.id.cmp(&b.id))
+    });
+
+    for (i, member) in members.iter_mut().enumerate() {
+        member.rank = i + 1;
+    }
+
+    Report {
+        event: leaderboard.event.clone(),
+        members,
+        days,
+    }
+}
+
+impl Report {
+    /// Renders the report as plain text tables
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        let name_width = self
+            .members
+            .iter()
+            .map(|m| m.name.chars().count())
+            .max()
+            .unwrap_or_default()
+            .max(6);
+
+        writeln!(out, "Advent of Code {}", self.event).unwrap();
+        writeln!(out).unwrap();
+        writeln!(
+            out,
+            "{:>4}  {:<name_width$}  {:>5}  {:>5}  {:>8}",
+            "Rank", "Member", "Stars", "Score", "Reported"
+        )
+        .unwrap();
+
+        for m in &self.members {
+            let mismatch = if m.local_score != m.reported_local_score {
+                " *"
+            } else {
+                ""
+            };
+
+            writeln!(
+                out,
+                "{:>4}  {:<name_width$}  {:>5}  {:>5}  {:>8}{}",
+                m.rank, m.name, m.stars, m.local_score, m.reported_local_score, mismatch
+            )
+            .unwrap();
+        }
+
+        if self
+            .members
+            .iter()
+            .any(|m| m.local_score != m.reported_local_score)
+        {
+            writeln!(
+                out,
+                "\n* The recomputed score differs from the reported one"
+            )
+            .unwrap();
+        }
+
+        let duration = |secs: Option<i64>| secs.map_or("-".to_string(), time::format_duration);
+
+        for day in &self.days {
+            writeln!(out).unwrap();
+            writeln!(
+                out,
+                "Day {} (unlocked {})",
+                day.day,
+                time::format_utc(day.unlocked_at)
+            )
+            .unwrap();
+            writeln!(
+                out,
+                "{:>4}  {:<name_width$}  {:>12}  {:>12}  {:>12}  {:>6}",
+                "Rank", "Member", "Part 1", "Part 2", "Delta", "Points"
+            )
+            .unwrap();
+
+            for r in &day.results {
+                writeln!(
+                    out,
+                    "{:>4}  {:<name_width$}  {:>12}  {:>12}  {:>12}  {:>6}",
+                    r.rank,
+                    r.name,
+                    duration(r.part_1_secs),
+                    duration(r.part_2_secs),
+                    duration(r.delta_secs),
+                    r.points
+                )
+                .unwrap();
+            }
+        }
+
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "alice", "stars": 4, "local_score": 10, "global_score": 0,
+                "last_star_ts": 1701495000,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701407100, "star_index": 10 },
+                        "2": { "get_star_ts": 1701407400, "star_index": 20 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1701494000, "star_index": 50 },
+                        "2": { "get_star_ts": 1701495000, "star_index": 60 }
+                    }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 3, "local_score": 9, "global_score": 0,
+                "last_star_ts": 1701493500,
+                "completion_day_level": {
+                    "1": {
+                        "1": { "get_star_ts": 1701407000, "star_index": 5 },
+                        "2": { "get_star_ts": 1701408000, "star_index": 30 }
+                    },
+                    "2": {
+                        "1": { "get_star_ts": 1701493500, "star_index": 40 }
+                    }
+                }
+            },
+            "3": {
+                "id": 3, "name": "carol", "stars": 0, "local_score": 0, "global_score": 0,
+                "last_star_ts": 0, "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    #[test]
+    fn recomputes_local_scores() {
+        let report = analyze(&Leaderboard::parse(EXAMPLE).unwrap());
+
+        let scores: Vec<_> = report
+            .members
+            .iter()
+            .map(|m| (m.rank, m.name.as_str(), m.stars, m.local_score))
+            .collect();
+
+        assert_eq!(
+            scores,
+            [
+                (1, "alice", 4, 10),
+                (2, "(anonymous user #2)", 3, 8),
+                (3, "carol", 0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn ranks_days() {
+        let report = analyze(&Leaderboard::parse(EXAMPLE).unwrap());
+        assert_eq!(report.days.len(), 2);
+
+        let day_1 = &report.days[0];
+        assert_eq!(day_1.day, 1);
+        assert_eq!(
+            day_1.results[0],
+            DayResult {
+                rank: 1,
+                id: 1,
+                name: "alice".to_string(),
+                part_1_secs: Some(300),
+                part_2_secs: Some(600),
+                delta_secs: Some(300),
+                points: 5,
+            }
+        );
+        assert_eq!(day_1.results[1].id, 2);
+        assert_eq!(day_1.results[1].points, 5);
+
+        // Both stars rank above a single star, however fast
+        let day_2 = &report.days[1];
+        assert_eq!(day_2.results[0].id, 1);
+        assert_eq!(day_2.results[1].part_2_secs, None);
+        assert_eq!(day_2.results[1].delta_secs, None);
+    }
+
+    #[test]
+    fn renders_tables() {
+        let text = analyze(&Leaderboard::parse(EXAMPLE).unwrap()).render();
+
+        assert!(text.contains("\n   1  alice                    4     10        10\n"));
+        assert!(text.contains("\n   2  (anonymous user #2)      3      8         9 *\n"));
+        assert!(text.contains("\nDay 1 (unlocked 2023-12-01 05:00:00 UTC)\n"));
+        assert!(text.contains(
+            "\n   2  (anonymous user #2)        5m 00s             -             -       3\n"
+        ));
+    }
+}
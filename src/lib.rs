@@ -1,44 +1,61 @@
-use std::str::FromStr;
-use std::fmt::Debug;
-
-#[macro_export]
-macro_rules! solution {
+#![cfg_attr(feature = "bench", feature(test))]
+
+#[cfg(feature = "bench")]
+extern crate test;
+
+pub mod answers;
+pub mod automaton;
+pub mod client;
+pub mod config;
+pub mod crypto;
+pub mod graph;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod leaderboard;
+pub mod manifest;
+pub mod math;
+pub mod normalize;
+pub mod parallel;
+pub mod parsing;
+pub mod progress;
+pub mod runner;
+#[cfg(test)]
+mod testing;
+pub mod time;
+
+pub use input::{
+    load_input_file, load_input_file_with, read_example_file, read_example_file_with,
+    read_input_file,
+};
+pub use normalize::Normalize;
+
+/// Declares the shared items of a day module: its number, its input
+/// normalisation policy, the example tests and the benchmarks
+macro_rules! day {
     ($day:expr) => {
-        #[cfg(feature = "bench")]
-        extern crate test;
-
-        use advent_of_code_2023::{read_example_file, read_input_file, Part};
-
-        const DAY: u32 = $day;
+        $crate::day!($day, $crate::Normalize::Lines);
+    };
+    ($day:expr, $normalize:expr) => {
+        pub const DAY: u32 = $day;
 
-        fn main() {
-            let input = read_input_file(DAY);
-
-            let start_time = std::time::Instant::now();
-            println!(
-                "Part 1: {:?} (in {:.2?})",
-                part_1(&input),
-                start_time.elapsed()
-            );
-
-            let start_time = std::time::Instant::now();
-            println!(
-                "Part 2: {:?} (in {:.2?})",
-                part_2(&input),
-                start_time.elapsed()
-            );
-        }
+        /// How this day's input is cleaned up before it is solved
+        pub const NORMALIZE: $crate::Normalize = $normalize;
 
         #[cfg(test)]
         mod tests {
             #[cfg(feature = "bench")]
             use test::{black_box, Bencher};
 
+            #[cfg(feature = "bench")]
+            use $crate::load_input_file_with;
+            use $crate::{read_example_file_with, Part};
+
             use super::*;
 
             #[test]
             fn part_1_example() {
-                let (expected_result, input) = read_example_file(DAY, Part::One);
+                let (expected_result, input) = read_example_file_with(DAY, Part::One, NORMALIZE);
                 let result = part_1(&input);
 
                 assert_eq!(result, Some(expected_result));
@@ -46,7 +63,7 @@ macro_rules! solution {
 
             #[test]
             fn part_2_example() {
-                let (expected_result, input) = read_example_file(DAY, Part::Two);
+                let (expected_result, input) = read_example_file_with(DAY, Part::Two, NORMALIZE);
                 let result = part_2(&input);
 
                 assert_eq!(result, Some(expected_result));
@@ -54,51 +71,51 @@ macro_rules! solution {
 
             #[cfg(feature = "bench")]
             #[bench]
-            fn bench_part_1(b: &mut test::Bencher) {
-                let input = read_input_file(DAY);
+            fn bench_part_1(b: &mut Bencher) {
+                let Ok(input) = load_input_file_with(DAY, NORMALIZE) else {
+                    return;
+                };
                 b.iter(|| black_box(part_1(&input)));
             }
 
             #[cfg(feature = "bench")]
             #[bench]
-            fn bench_part_2(b: &mut test::Bencher) {
-                let input = read_input_file(DAY);
+            fn bench_part_2(b: &mut Bencher) {
+                let Ok(input) = load_input_file_with(DAY, NORMALIZE) else {
+                    return;
+                };
                 b.iter(|| black_box(part_2(&input)));
             }
         }
     };
 }
 
-#[derive(Debug, Clone, Copy)]
-#[repr(u8)]
-pub enum Part {
-    One = 1,
-    Two = 2,
-}
-
-pub fn read_input_file(day: u32) -> String {
-    let path = format!("data/inputs/{:02}.txt", day);
-    std::fs::read_to_string(path).unwrap().trim().to_string()
-}
+pub(crate) use day;
 
-pub fn read_example_file<T: FromStr>(day: u32, part: Part) -> (T, String)
-where
-    <T as FromStr>::Err: Debug,
-{
-    let path = format!("data/examples/{:02}/part-{:01}.txt", day, part as u8);
-    let file = std::fs::read_to_string(&path)
-        .unwrap_or_else(|_| panic!("Example file not found: {}", path));
+pub mod days;
 
-    let (expected_result, input) = file
-        .split_once("\n---\n")
-        .expect("Example file is not in the correct format");
+/// Defines the `main` function of a day's binary, which runs the solution
+/// from the matching [`days`] module. Any hooks the module provides, such as
+/// `explain` or `query`, can be listed after the module name.
+#[macro_export]
+macro_rules! solution {
+    ($day:ident $(, $hook:ident)*) => {
+        use advent_of_code_2023::days::$day::{part_1, part_2, DAY, NORMALIZE};
 
-    let expected_result = expected_result
-        .trim()
-        .parse::<T>()
-        .expect("Expected result is not a number");
+        fn main() {
+            let hooks = advent_of_code_2023::runner::Hooks {
+                $($hook: Some(advent_of_code_2023::days::$day::$hook),)*
+                ..Default::default()
+            };
 
-    let input = input.trim().to_string();
+            advent_of_code_2023::runner::run_with_hooks(DAY, NORMALIZE, part_1, part_2, hooks);
+        }
+    };
+}
 
-    (expected_result, input)
+#[derive(Debug, Clone, Copy)]
+#[repr(u8)]
+pub enum Part {
+    One = 1,
+    Two = 2,
 }
@@ -1,36 +1,411 @@
-use std::{fs, path::Path};
+use std::{
+    fs,
+    io::{self, Write},
+    path::Path,
+    process::{Command, Stdio},
+};
 
-use aoc::Client;
+use advent_of_code_2023::{
+    answers::Answers,
+    client::{Client, ClientError},
+    config::{Config, SessionSource},
+    crypto::{InputKey, KEY_FILE},
+    input::{encrypted_input_path, input_path, load_raw_input_file, InputError},
+    leaderboard::{self, Leaderboard},
+    manifest::{self, Manifest, Verification},
+    progress::{self, DayStatus, PartStatus},
+    runner::{OutputFormat, PartReport, Status},
+    time, Part,
+};
 
-fn main() {
-    let session_token = std::env::var("AOC_SESSION_TOKEN")
-        .unwrap_or_else(|_| abort!("AOC_SESSION_TOKEN environment variable not set"));
+const DOWNLOAD_FLAGS: &[&str] = &["--encrypt", "--refresh"];
+
+const USAGE: &str = "\
+Usage: cargo run [-- COMMAND]
 
-    print!("Initializing AOC client connection...");
+Commands:
+    [--encrypt] [--refresh]                      Download every unlocked input (the default)
+    encrypt                                      Encrypt the inputs already downloaded
+    keygen                                       Create a new input key
+    verify                                       Check the inputs against the manifest
+    whoami                                       Show the account the session belongs to
+    leaderboard <FILE | --id ID> [--format F]    Show a private leaderboard
+    status [--format F] [--sync-readme]          Show which parts are solved
+    run-all [--examples]                         Run every day and print its records as NDJSON
+    answer <DAY> <PART> <ANSWER>                 Record an answer that was accepted";
+
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
 
-    let client = Client::new("2023", session_token)
-        .unwrap_or_else(|_| abort!("Failed to create AOC client"));
+    match args.as_slice() {
+        ["encrypt"] => encrypt_existing(),
+        ["keygen"] => keygen(),
+        ["verify"] => verify(),
+        ["whoami"] => whoami(),
+        ["leaderboard", args @ ..] => show_leaderboard(args),
+        ["status", args @ ..] => status(args),
+        ["run-all", args @ ..] => run_all(args),
+        ["answer", day, part, answer] => record_answer(day, part, answer),
+        flags if flags.iter().all(|f| DOWNLOAD_FLAGS.contains(f)) => {
+            download(flags.contains(&"--encrypt"), flags.contains(&"--refresh"))
+        }
+        _ => abort!("{}", USAGE),
+    }
+}
 
-    println!("OK");
+/// Downloads every unlocked input, optionally writing an encrypted copy next
+/// to each one. Responses are cached unless `refresh` is set.
+fn download(encrypt: bool, refresh: bool) {
+    let key = encrypt.then(load_key);
+    let (client, source) = connect();
+    let mut client = client.refresh(refresh);
 
     if !Path::new("./data/inputs").exists() {
         fs::create_dir("./data/inputs").unwrap();
     }
 
+    let mut manifest =
+        Manifest::load().unwrap_or_else(|e| abort!("Failed to read the manifest: {}", e));
+
     for day in 1..=25 {
         print!("Downloading day {:02}...", day);
 
-        let Ok(input) = client.get_input(day) else {
-            abort!("FAILED");
+        let input = match client.get_input(day) {
+            Ok(input) => input,
+            Err(e @ ClientError::Locked { .. }) => {
+                println!("SKIPPED ({})", e);
+                break;
+            }
+            Err(e @ ClientError::SessionExpired) => {
+                abort!("FAILED ({})\nThe token was read from {}", e, source)
+            }
+            Err(e) => abort!("FAILED ({})", e),
         };
 
-        let path = format!("./data/inputs/{:02}.txt", day);
-        fs::write(path, input).unwrap();
+        fs::write(input_path(day), &input.body).unwrap();
+
+        if let Some(key) = &key {
+            fs::write(encrypted_input_path(day), key.encrypt(day, &input.body)).unwrap();
+        }
+
+        if !input.cached || manifest.get(day).is_none() {
+            manifest.record(day, &input.body, time::now());
+            manifest.save().unwrap();
+        }
+
+        println!("{}", if input.cached { "OK (cached)" } else { "OK" });
+    }
+}
+
+/// Checks that the session token is accepted and prints who it belongs to
+fn whoami() {
+    let (mut client, source) = connect();
+
+    match client.whoami() {
+        Ok(user) => println!("Logged in as {} (session token from {})", user, source),
+        Err(e @ ClientError::SessionExpired) => {
+            abort!("Not logged in: {}\nThe token was read from {}", e, source)
+        }
+        Err(e) => abort!("Failed to check the session token: {}", e),
+    }
+}
+
+/// Analyses a private leaderboard, read from an exported JSON file or
+/// fetched from the API
+fn show_leaderboard(args: &[&str]) {
+    const USAGE: &str = "Usage: cargo run -- leaderboard <FILE | --id ID> [--format text|json]";
+
+    let (source, format) = match args {
+        [source @ .., "--format", format] => (source, *format),
+        source => (source, "text"),
+    };
+
+    let format: OutputFormat = format
+        .parse()
+        .unwrap_or_else(|e| abort!("{}\n{}", e, USAGE));
+
+    let json = match source {
+        ["--id", id] => {
+            let id = id
+                .parse()
+                .unwrap_or_else(|_| abort!("Invalid leaderboard id `{}`", id));
+            let (mut client, _) = connect();
+
+            client
+                .get_leaderboard(id)
+                .unwrap_or_else(|e| abort!("Failed to fetch leaderboard {}: {}", id, e))
+                .body
+        }
+        [path] => {
+            fs::read_to_string(path).unwrap_or_else(|e| abort!("Failed to read {}: {}", path, e))
+        }
+        _ => abort!("{}", USAGE),
+    };
+
+    let leaderboard =
+        Leaderboard::parse(&json).unwrap_or_else(|e| abort!("Invalid leaderboard JSON: {}", e));
+    let report = leaderboard::analyze(&leaderboard);
+
+    match format {
+        OutputFormat::Text => print!("{}", report.render()),
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&report).unwrap()),
+        OutputFormat::Ndjson => println!("{}", serde_json::to_string(&report).unwrap()),
+    }
+}
+
+/// Records an answer that was accepted on adventofcode.com
+fn record_answer(day: &str, part: &str, answer: &str) {
+    let day = day
+        .parse()
+        .ok()
+        .filter(|d| (1..=25).contains(d))
+        .unwrap_or_else(|| abort!("Invalid day `{}`", day));
+    let part = match part {
+        "1" => Part::One,
+        "2" => Part::Two,
+        _ => abort!("Invalid part `{}`", part),
+    };
+
+    let mut answers =
+        Answers::load().unwrap_or_else(|e| abort!("Failed to read the answers: {}", e));
+    answers.record(day, part, answer);
+    answers.save().unwrap();
+
+    println!("Recorded day {:02} part {}: {}", day, part as u8, answer);
+}
+
+/// Reports the status of every part by running each solution against its
+/// examples and its input, optionally updating the results table in the README
+fn status(args: &[&str]) {
+    const USAGE: &str = "Usage: cargo run -- status [--format text|json] [--sync-readme]";
+
+    let mut format = OutputFormat::Text;
+    let mut sync_readme = false;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--sync-readme" => sync_readme = true,
+            "--format" => {
+                format = args
+                    .next()
+                    .unwrap_or_else(|| abort!("{}", USAGE))
+                    .parse()
+                    .unwrap_or_else(|e| abort!("{}\n{}", e, USAGE))
+            }
+            _ => abort!("{}", USAGE),
+        }
+    }
+
+    let days: Vec<DayStatus> = (1..=25).map(day_status).collect();
+
+    match format {
+        OutputFormat::Text => {
+            for day in days.iter().filter(|d| d.is_started()) {
+                println!(
+                    "Day {:02}: part 1 {}, part 2 {}",
+                    day.day, day.part_1, day.part_2
+                );
+            }
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&days).unwrap()),
+        OutputFormat::Ndjson => {
+            for day in &days {
+                println!("{}", serde_json::to_string(day).unwrap());
+            }
+        }
+    }
+
+    if sync_readme {
+        let readme = fs::read_to_string("README.md").unwrap();
+        let readme = progress::sync_readme(&readme, &progress::results_table(&days))
+            .unwrap_or_else(|| abort!("README.md has no results table"));
 
+        fs::write("README.md", readme).unwrap();
+    }
+}
+
+/// Runs every day that has a solution and prints the reports of all of them
+/// as NDJSON. Exits with a non-zero status if any part failed.
+fn run_all(args: &[&str]) {
+    let examples = match args {
+        [] => false,
+        ["--examples"] => true,
+        _ => abort!("Usage: cargo run -- run-all [--examples]"),
+    };
+
+    let mut failed = false;
+
+    for day in (1..=25).filter(|day| Path::new(&format!("src/bin/{:02}.rs", day)).exists()) {
+        for report in run_day(day, examples) {
+            failed |= matches!(report.status, Status::Error | Status::Incorrect);
+            println!("{}", serde_json::to_string(&report).unwrap());
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn day_status(day: u32) -> DayStatus {
+    let unimplemented = DayStatus {
+        day,
+        part_1: PartStatus::Unimplemented,
+        part_2: PartStatus::Unimplemented,
+    };
+
+    if !Path::new(&format!("src/bin/{:02}.rs", day)).exists() {
+        return unimplemented;
+    }
+
+    let examples = run_day(day, true);
+    let actual = run_day(day, false);
+    let find = |reports: &[PartReport], part: u8| -> Option<PartReport> {
+        reports.iter().find(|r| r.part == part).cloned()
+    };
+    let part_status = |part| {
+        PartStatus::from_reports(find(&examples, part).as_ref(), find(&actual, part).as_ref())
+    };
+
+    DayStatus {
+        part_1: part_status(1),
+        part_2: part_status(2),
+        ..unimplemented
+    }
+}
+
+/// Runs a day's binary and collects its reports. Solutions that fail to
+/// build or crash produce no reports.
+fn run_day(day: u32, examples: bool) -> Vec<PartReport> {
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let bin = format!("{:02}", day);
+
+    let mut command = Command::new(cargo);
+    command.args(["run", "--quiet", "--release", "--bin", &bin, "--"]);
+    command.args(["--format", "ndjson"]);
+    if examples {
+        command.arg("--examples");
+    }
+
+    let Ok(output) = command.stderr(Stdio::inherit()).output() else {
+        return Vec::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Creates a client from the config file and the session token
+fn connect() -> (Client, SessionSource) {
+    let config = Config::load().unwrap_or_else(|e| abort!("{}", e));
+    let (session_token, source) = config.session_token().unwrap_or_else(|e| abort!("{}", e));
+
+    if config.downloader.contact.is_none() {
+        println!("Note: set `downloader.contact` in the config file to identify yourself");
+    }
+
+    let client = Client::new(session_token, &config.downloader)
+        .unwrap_or_else(|e| abort!("Failed to create AOC client: {}", e));
+
+    (client, source)
+}
+
+/// Checks every available input against the manifest. Exits with a non-zero
+/// status if any input has changed or looks like an error page.
+fn verify() {
+    let manifest =
+        Manifest::load().unwrap_or_else(|e| abort!("Failed to read the manifest: {}", e));
+    let mut failed = false;
+
+    for day in 1..=25 {
+        let input = match load_raw_input_file(day) {
+            Ok(input) => input,
+            Err(InputError::NotFound { .. }) => {
+                if manifest.get(day).is_some() {
+                    println!("Day {:02}: missing", day);
+                }
+                continue;
+            }
+            Err(e) => {
+                println!("Day {:02}: ERROR {}", day, e);
+                failed = true;
+                continue;
+            }
+        };
+
+        let verification = manifest.verify(day, &input);
+        failed |= matches!(verification, Verification::Mismatch { .. });
+
+        match manifest::suspicious_content(&input) {
+            Some(reason) => {
+                println!("Day {:02}: {}, {}", day, verification, reason);
+                failed = true;
+            }
+            None => println!("Day {:02}: {}", day, verification),
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// Writes encrypted copies of the inputs that have already been downloaded
+fn encrypt_existing() {
+    let key = load_key();
+
+    for day in 1..=25 {
+        let Ok(input) = fs::read_to_string(input_path(day)) else {
+            continue;
+        };
+
+        print!("Encrypting day {:02}...", day);
+        fs::write(encrypted_input_path(day), key.encrypt(day, &input)).unwrap();
         println!("OK");
     }
 }
 
+fn load_key() -> InputKey {
+    match InputKey::load() {
+        Ok(Some(key)) => key,
+        Ok(None) => abort!("No input key found. Run `cargo run -- keygen` to create one"),
+        Err(e) => abort!("Invalid input key: {}", e),
+    }
+}
+
+/// Creates a new input key. Refuses to overwrite an existing key, since
+/// inputs encrypted with it could no longer be read.
+fn keygen() {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+
+    let mut file = match options.open(KEY_FILE) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            abort!("{} already exists", KEY_FILE)
+        }
+        Err(e) => abort!("Failed to create {}: {}", KEY_FILE, e),
+    };
+
+    writeln!(file, "{}", InputKey::generate().to_hex())
+        .unwrap_or_else(|e| abort!("Failed to write {}: {}", KEY_FILE, e));
+
+    println!(
+        "Wrote a new input key to {}. Keep it out of version control.",
+        KEY_FILE
+    );
+}
+
 #[macro_export]
 macro_rules! abort {
     ($($arg:tt)*) => {
@@ -0,0 +1,183 @@
+use std::{collections::BTreeMap, fmt, fs, io, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Where the manifest is stored. It holds no puzzle content, so it can be committed.
+pub fn manifest_path() -> PathBuf {
+    PathBuf::from("data/inputs/manifest.json")
+}
+
+/// What was recorded about an input when it was downloaded
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Entry {
+    pub sha256: String,
+    pub size: u64,
+    /// Seconds since the Unix epoch
+    pub downloaded_at: i64,
+}
+
+impl Entry {
+    pub fn new(content: &str, downloaded_at: i64) -> Self {
+        Self {
+            sha256: sha256_hex(content),
+            size: content.len() as u64,
+            downloaded_at,
+        }
+    }
+}
+
+/// Checksums of the downloaded inputs, keyed by day
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Manifest(BTreeMap<u32, Entry>);
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verification {
+    Ok,
+    /// The manifest has no entry for the day
+    Unrecorded,
+    /// The input differs from what was downloaded
+    Mismatch {
+        expected: Entry,
+        size: u64,
+    },
+}
+
+impl Manifest {
+    /// Loads the manifest, returning an empty one if it does not exist yet
+    pub fn load() -> io::Result<Self> {
+        match fs::read_to_string(manifest_path()) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("manifest is always serializable");
+        fs::write(manifest_path(), json + "\n")
+    }
+
+    pub fn get(&self, day: u32) -> Option<&Entry> {
+        self.0.get(&day)
+    }
+
+    pub fn record(&mut self, day: u32, content: &str, downloaded_at: i64) {
+        self.0.insert(day, Entry::new(content, downloaded_at));
+    }
+
+    pub fn verify(&self, day: u32, content: &str) -> Verification {
+        let Some(expected) = self.get(day) else {
+            return Verification::Unrecorded;
+        };
+
+        if expected.size == content.len() as u64 && expected.sha256 == sha256_hex(content) {
+            Verification::Ok
+        } else {
+            Verification::Mismatch {
+                expected: expected.clone(),
+                size: content.len() as u64,
+            }
+        }
+    }
+}
+
+impl fmt::Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verification::Ok => write!(f, "OK"),
+            Verification::Unrecorded => write!(f, "not in manifest"),
+            Verification::Mismatch { expected, size } => write!(
+                f,
+                "MISMATCH (expected {} bytes downloaded {}, found {} bytes)",
+                expected.size,
+                crate::time::format_utc(expected.downloaded_at),
+                size
+            ),
+        }
+    }
+}
+
+pub fn sha256_hex(content: &str) -> String {
+    Sha256::digest(content.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Markers of pages the server returns instead of an input
+const SUSPICIOUS_MARKERS: &[&str] = &[
+    "<!DOCTYPE",
+    "<html",
+    "Puzzle inputs differ by user",
+    "Please log in",
+    "Please don't repeatedly request this endpoint before it unlocks",
+    "404 Not Found",
+];
+
+/// Returns a description of why the content does not look like a puzzle
+/// input (an HTML page, a login prompt or an empty file), if it doesn't
+pub fn suspicious_content(content: &str) -> Option<String> {
+    if content.trim().is_empty() {
+        return Some("input is empty".to_string());
+    }
+
+    let head: String = content.chars().take(512).collect();
+    let head_lower = head.to_ascii_lowercase();
+
+    SUSPICIOUS_MARKERS
+        .iter()
+        .find(|marker| head_lower.contains(&marker.to_ascii_lowercase()))
+        .map(|marker| format!("input looks like an error page (contains `{}`)", marker))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verifies_recorded_inputs() {
+        let mut manifest = Manifest::default();
+        assert_eq!(manifest.verify(1, "1abc2"), Verification::Unrecorded);
+
+        manifest.record(1, "1abc2\n", 1_701_406_800);
+        assert_eq!(manifest.verify(1, "1abc2\n"), Verification::Ok);
+        assert!(matches!(
+            manifest.verify(1, "1abc3\n"),
+            Verification::Mismatch { size: 6, .. }
+        ));
+        assert!(matches!(
+            manifest.verify(1, "1abc2"),
+            Verification::Mismatch { size: 5, .. }
+        ));
+
+        let json = serde_json::to_string(&manifest).unwrap();
+        assert!(json.starts_with(r#"{"1":{"sha256":""#));
+        assert_eq!(serde_json::from_str::<Manifest>(&json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn hashes() {
+        assert_eq!(
+            sha256_hex("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn detects_error_pages() {
+        assert_eq!(suspicious_content("1abc2\npqr3stu8vwx"), None);
+        assert!(suspicious_content("").is_some());
+        assert!(suspicious_content("<!DOCTYPE html>\n<html lang=\"en-us\">").is_some());
+        assert!(suspicious_content(
+            "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+        )
+        .is_some());
+        assert!(suspicious_content(
+            "Please don't repeatedly request this endpoint before it unlocks! The calendar countdown is synchronized with the server time; the link will be enabled on the calendar the instant this puzzle becomes available.\n"
+        )
+        .is_some());
+    }
+}
@@ -0,0 +1,346 @@
+use std::ops::RangeInclusive;
+
+use num::{CheckedAdd, CheckedMul, Integer, PrimInt, Unsigned};
+
+/// Extended Euclidean algorithm, returning `(g, x, y)` such that `a*x + b*y = g = gcd(a, b)`
+pub fn ext_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    if b == 0 {
+        return (a.abs(), a.signum(), 0);
+    }
+
+    let (g, x, y) = ext_gcd(b, a.rem_euclid(b));
+    (g, y, x - a.div_euclid(b) * y)
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` where the
+/// moduli do not have to be coprime.
+///
+/// Returns the smallest non-negative solution and the modulus it repeats
+/// with (the LCM of the moduli), or `None` if the system is inconsistent or
+/// the combined modulus does not fit in a `u128`.
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Option<(u128, u128)> {
+    let mut x: u128 = 0;
+    let mut m: u128 = 1;
+
+    for (residue, modulus) in congruences {
+        assert!(modulus > 0, "modulus must be positive");
+
+        let modulus = modulus as u128;
+        let residue = residue as u128 % modulus;
+
+        // Solve x + m*k ≡ residue (mod modulus) for k
+        let (g, inverse, _) = ext_gcd((m % modulus) as i128, modulus as i128);
+        let g = g as u128;
+
+        let difference = (residue + modulus - x % modulus) % modulus;
+        if !difference.is_multiple_of(g) {
+            return None;
+        }
+
+        let reduced_modulus = modulus / g;
+        let inverse = inverse.rem_euclid(reduced_modulus as i128) as u128;
+        let k = mul_mod_u128(difference / g, inverse, reduced_modulus);
+
+        let lcm = m.checked_mul(reduced_modulus)?;
+        x = (x + m.checked_mul(k)?) % lcm;
+        m = lcm;
+    }
+
+    Some((x, m))
+}
+
+/// Finds the first time at which several eventually-periodic events happen
+/// together, where each event first happens at `offset` and then repeats
+/// every `period` steps.
+///
+/// Unlike a plain LCM this does not assume that every offset is a multiple of its period.
+pub fn first_common_time(events: &[(u64, u64)]) -> Option<u128> {
+    let (x, m) = crt(events.iter().copied())?;
+    let earliest = events.iter().map(|(offset, _)| *offset as u128).max()?;
+
+    if x >= earliest {
+        return Some(x);
+    }
+
+    // Step forward whole periods until every event has started repeating
+    let periods = (earliest - x).div_ceil(m);
+    x.checked_add(periods.checked_mul(m)?)
+}
+
+/// Computes `a * b mod m` without overflowing
+pub fn mul_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % m;
+    }
+
+    // Fall back to double-and-add when the product would overflow
+    let mut result = 0;
+    let mut a = a % m;
+    let mut b = b % m;
+
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod_u128(result, a, m);
+        }
+
+        a = add_mod_u128(a, a, m);
+        b >>= 1;
+    }
+
+    result
+}
+
+fn add_mod_u128(a: u128, b: u128, m: u128) -> u128 {
+    // a and b are both less than m, so a - (m - b) cannot underflow when a >= m - b
+    if a >= m - b {
+        a - (m - b)
+    } else {
+        a + b
+    }
+}
+
+/// Computes `a * b mod m` for 64-bit values
+pub fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    ((a as u128 * b as u128) % m as u128) as u64
+}
+
+/// Computes `base ^ exponent mod m` by repeated squaring
+pub fn pow_mod(base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    let mut base = base % m;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+
+    result
+}
+
+/// The exact integer square root (the largest `r` such that `r * r <= n`)
+pub fn isqrt<T: PrimInt + Unsigned>(n: T) -> T {
+    if n < T::from(2).unwrap() {
+        return n;
+    }
+
+    // Start from a power of two above the root so that Newton's method
+    // decreases monotonically onto the answer
+    let bits = T::zero().count_zeros() - n.leading_zeros();
+    let mut x = T::one() << (bits.div_ceil(2) as usize);
+
+    loop {
+        let next = (x + n / x) >> 1;
+
+        if next >= x {
+            return x;
+        }
+
+        x = next;
+    }
+}
+
+/// Finds the integers `x` for which `a*x^2 + b*x + c < 0`, where `a > 0`.
+///
+/// Roots are approximated with an exact integer square root and then
+/// corrected by evaluating the polynomial, so no floating point is involved.
+/// Returns an empty range if no integer satisfies the inequality, or `None`
+/// if the arithmetic overflows an `i128`.
+pub fn quadratic_below_zero(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    assert!(a > 0, "the quadratic must open upwards");
+
+    let f = |x: i128| {
+        a.checked_mul(x)?
+            .checked_mul(x)?
+            .checked_add(b.checked_mul(x)?)?
+            .checked_add(c)
+    };
+
+    let discriminant = b
+        .checked_mul(b)?
+        .checked_sub(a.checked_mul(c)?.checked_mul(4)?)?;
+    if discriminant <= 0 {
+        return Some(RangeInclusive::new(1, 0));
+    }
+
+    let root = isqrt(discriminant as u128) as i128;
+    let two_a = a.checked_mul(2)?;
+
+    // Approximate the real roots (these are within one step of the integer bounds)
+    let mut low = b.checked_neg()?.checked_sub(root)?.div_euclid(two_a);
+    let mut high = b.checked_neg()?.checked_add(root)?.div_euclid(two_a) + 1;
+
+    // Move the bounds inwards until they satisfy the inequality
+    while low <= high && f(low)? >= 0 {
+        low += 1;
+    }
+    while high >= low && f(high)? >= 0 {
+        high -= 1;
+    }
+
+    if low > high {
+        return Some(RangeInclusive::new(1, 0));
+    }
+
+    // Then outwards in case the approximations were too tight
+    while f(low - 1)? < 0 {
+        low -= 1;
+    }
+    while f(high + 1)? < 0 {
+        high += 1;
+    }
+
+    Some(low..=high)
+}
+
+/// Sums an iterator, returning `None` on overflow
+pub fn checked_sum<T: CheckedAdd + num::Zero>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::zero(), |acc, v| acc.checked_add(&v))
+}
+
+/// Multiplies an iterator together, returning `None` on overflow
+pub fn checked_product<T: CheckedMul + num::One>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::one(), |acc, v| acc.checked_mul(&v))
+}
+
+/// Computes the LCM of two numbers, returning `None` on overflow
+pub fn checked_lcm<T: PrimInt + Integer>(a: T, b: T) -> Option<T> {
+    if a.is_zero() || b.is_zero() {
+        return Some(T::zero());
+    }
+
+    (a / a.gcd(&b)).checked_mul(&b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extended_gcd() {
+        for (a, b) in [(240, 46), (46, 240), (7, 0), (0, 7), (-12, 18)] {
+            let (g, x, y) = ext_gcd(a, b);
+            assert_eq!(g, num::integer::gcd(a, b));
+            assert_eq!(a * x + b * y, g);
+        }
+    }
+
+    #[test]
+    fn crt_with_coprime_moduli() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn crt_with_shared_factors() {
+        // x ≡ 2 (mod 4) and x ≡ 4 (mod 6) -> x ≡ 10 (mod 12)
+        assert_eq!(crt([(2, 4), (4, 6)]), Some((10, 12)));
+
+        // x ≡ 1 (mod 4) and x ≡ 2 (mod 6) has no solution (parities differ)
+        assert_eq!(crt([(1, 4), (2, 6)]), None);
+
+        // Residues larger than the modulus are reduced first
+        assert_eq!(crt([(6, 4), (16, 6)]), Some((10, 12)));
+    }
+
+    #[test]
+    fn crt_with_large_moduli() {
+        let primes = [1_000_000_007u64, 998_244_353, 1_000_000_009];
+        let x: u128 = 123_456_789_012_345_678_901_234;
+        let congruences = primes.map(|p| ((x % p as u128) as u64, p));
+
+        let modulus = primes.iter().map(|p| *p as u128).product::<u128>();
+        assert_eq!(crt(congruences), Some((x % modulus, modulus)));
+
+        // The combined modulus overflows a u128
+        assert_eq!(
+            crt([(0, u64::MAX), (0, u64::MAX - 1), (0, u64::MAX - 2)]),
+            None
+        );
+    }
+
+    #[test]
+    fn common_time_respects_offsets() {
+        // Aligned cycles (the LCM case)
+        assert_eq!(first_common_time(&[(2, 2), (3, 3)]), Some(6));
+
+        // Offsets that are not multiples of their periods
+        assert_eq!(first_common_time(&[(3, 5), (4, 7)]), Some(18));
+
+        // The CRT solution comes before one of the events has started
+        assert_eq!(first_common_time(&[(1, 2), (15, 4)]), Some(15));
+        assert_eq!(first_common_time(&[(1, 2), (16, 4)]), None);
+        assert_eq!(first_common_time(&[]), None);
+    }
+
+    #[test]
+    fn modular_arithmetic() {
+        assert_eq!(mul_mod(u64::MAX, u64::MAX, 1_000_000_007), 114_944_269);
+        assert_eq!(pow_mod(2, 10, 1000), 24);
+        assert_eq!(pow_mod(3, 0, 1), 0);
+        assert_eq!(mul_mod_u128(u128::MAX, u128::MAX, u128::MAX - 1), 1);
+        assert_eq!(mul_mod_u128(1 << 100, 1 << 100, (1 << 127) + 1), {
+            // 2^200 = (2^127)^1 * 2^73 ≡ -2^73 (mod 2^127 + 1)
+            (1u128 << 127) + 1 - (1 << 73)
+        });
+    }
+
+    #[test]
+    fn exact_integer_square_roots() {
+        for n in 0u64..10_000 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({})", n);
+        }
+
+        assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        assert_eq!(isqrt((1u128 << 100) - 1), (1 << 50) - 1);
+
+        // f64::sqrt rounds this up to u32::MAX
+        let n: u64 = (u32::MAX as u64) * (u32::MAX as u64) - 1;
+        assert_eq!(isqrt(n), u32::MAX as u64 - 1);
+    }
+
+    #[test]
+    fn quadratic_inequalities() {
+        // Day 6 example: charge time x beats the record when x * (7 - x) > 9,
+        // i.e. x^2 - 7x + 9 < 0
+        assert_eq!(quadratic_below_zero(1, -7, 9), Some(2..=5));
+        assert_eq!(quadratic_below_zero(1, -30, 200), Some(11..=19));
+
+        // Touching the x axis at a single integer root is not strictly below zero
+        let no_solutions = |a, b, c| quadratic_below_zero(a, b, c).unwrap().is_empty();
+        assert!(no_solutions(1, -4, 4));
+        assert!(no_solutions(1, 0, 1));
+
+        // Real roots without an integer between them
+        assert!(no_solutions(4, -6, 2));
+
+        // The discriminant does not fit in an i128
+        assert_eq!(quadratic_below_zero(1, i128::MAX / 2, 1), None);
+        assert_eq!(quadratic_below_zero(1, -(u64::MAX as i128), 1), None);
+
+        // Large coefficients that break f64 based approaches
+        let t: i128 = 60_000_000_000_000;
+        let record = t * t / 4 - 1;
+        assert_eq!(quadratic_below_zero(1, -t, record), Some(t / 2..=t / 2));
+    }
+
+    #[test]
+    fn checked_helpers() {
+        assert_eq!(checked_sum([1u8, 2, 3]), Some(6));
+        assert_eq!(checked_sum([200u8, 100]), None);
+        assert_eq!(checked_product([2u32, 3, 7]), Some(42));
+        assert_eq!(checked_product([u32::MAX, 2]), None);
+        assert_eq!(checked_lcm(4u64, 6), Some(12));
+        assert_eq!(checked_lcm(0u64, 6), Some(0));
+        assert_eq!(checked_lcm(u64::MAX, u64::MAX - 1), None);
+    }
+}
@@ -0,0 +1,45 @@
+/// How an input is cleaned up before it is handed to a solution. Line
+/// endings are always converted to `\n`, so Windows checkouts parse the same.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Normalize {
+    /// Strip exactly one trailing newline and keep all other whitespace
+    #[default]
+    Lines,
+    /// Strip all leading and trailing whitespace
+    Trim,
+    /// Only convert the line endings
+    Exact,
+}
+
+impl Normalize {
+    pub fn apply(self, input: &str) -> String {
+        let input = input.replace("\r\n", "\n");
+
+        match self {
+            Normalize::Lines => match input.strip_suffix('\n') {
+                Some(stripped) => stripped.to_string(),
+                None => input,
+            },
+            Normalize::Trim => input.trim().to_string(),
+            Normalize::Exact => input,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_inputs() {
+        let input = "  #.#\r\n  ..#\r\n\r\n";
+
+        assert_eq!(Normalize::Lines.apply(input), "  #.#\n  ..#\n");
+        assert_eq!(Normalize::Trim.apply(input), "#.#\n  ..#");
+        assert_eq!(Normalize::Exact.apply(input), "  #.#\n  ..#\n\n");
+
+        assert_eq!(Normalize::Lines.apply("1abc2\n"), "1abc2");
+        assert_eq!(Normalize::Lines.apply("1abc2"), "1abc2");
+        assert_eq!(Normalize::Lines.apply(""), "");
+    }
+}
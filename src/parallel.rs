@@ -0,0 +1,92 @@
+use std::{
+    iter::Sum,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+use rayon::prelude::*;
+
+/// Environment variable that forces the sequential code paths (e.g. for
+/// `cargo bench`, which does not go through the runner options)
+pub const SEQUENTIAL_ENV_VAR: &str = "AOC_SEQUENTIAL";
+
+const UNSET: u8 = 0;
+const PARALLEL: u8 = 1;
+const SEQUENTIAL: u8 = 2;
+
+static MODE: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Whether solutions should avoid rayon entirely
+pub fn is_sequential() -> bool {
+    match MODE.load(Ordering::Relaxed) {
+        UNSET => {
+            let sequential = std::env::var(SEQUENTIAL_ENV_VAR).is_ok_and(|v| v != "0");
+            set_sequential(sequential);
+            sequential
+        }
+        mode => mode == SEQUENTIAL,
+    }
+}
+
+pub fn set_sequential(sequential: bool) {
+    let mode = if sequential { SEQUENTIAL } else { PARALLEL };
+    MODE.store(mode, Ordering::Relaxed);
+}
+
+/// Sets the size of rayon's global thread pool. This can only be done once,
+/// before anything has run on the pool.
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|e| format!("Failed to set the number of threads: {}", e))
+}
+
+/// Maps every item, in parallel unless sequential mode is on. The results
+/// are in the same order as the items either way.
+pub fn map<I, T, R>(items: I, f: impl Fn(T) -> R + Sync + Send) -> Vec<R>
+where
+    I: IntoParallelIterator<Item = T> + IntoIterator<Item = T>,
+    T: Send,
+    R: Send,
+{
+    if is_sequential() {
+        items.into_iter().map(f).collect()
+    } else {
+        items.into_par_iter().map(f).collect()
+    }
+}
+
+/// Maps every item and sums the results, in parallel unless sequential mode is on
+pub fn sum<I, T, R>(items: I, f: impl Fn(T) -> R + Sync + Send) -> R
+where
+    I: IntoParallelIterator<Item = T> + IntoIterator<Item = T>,
+    T: Send,
+    R: Send + Sum,
+{
+    if is_sequential() {
+        items.into_iter().map(f).sum()
+    } else {
+        items.into_par_iter().map(f).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_results_either_way() {
+        let lines: Vec<_> = "1\n22\n333\n4444".lines().collect();
+
+        // Tests run concurrently, so only one test may change the mode
+        for sequential in [false, true, false] {
+            set_sequential(sequential);
+            assert_eq!(is_sequential(), sequential);
+
+            assert_eq!(map(&lines, |l| l.len()), [1, 2, 3, 4]);
+            assert_eq!(sum(&lines, |l| l.parse::<u32>().unwrap()), 4800);
+            assert_eq!(sum(0..1000u64, |i| i), 499_500);
+            assert_eq!(map(vec!["a", "b"], str::to_uppercase), ["A", "B"]);
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use std::str::FromStr;
+
+use nom::{
+    character::complete::{char, digit1, line_ending, one_of, space0, space1},
+    combinator::{map_res, opt, recognize},
+    multi::{many1, separated_list1},
+    sequence::{pair, preceded, separated_pair},
+    IResult, Parser,
+};
+
+/// Parses an unsigned integer of any width, failing if it does not fit in `T`
+pub fn parse_uint<T>(input: &str) -> IResult<&str, T>
+where
+    T: FromStr + num::Unsigned,
+{
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parses a signed integer of any width with an optional leading sign,
+/// failing if it does not fit in `T`
+pub fn parse_int<T>(input: &str) -> IResult<&str, T>
+where
+    T: FromStr + num::Signed,
+{
+    map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse)(input)
+}
+
+/// Parses one or more items separated by horizontal whitespace
+pub fn list<'a, T, F>(item: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    F: Parser<&'a str, T, nom::error::Error<&'a str>>,
+{
+    separated_list1(space1, item)
+}
+
+/// Parses one or more unsigned integers separated by horizontal whitespace
+pub fn uint_list<T>(input: &str) -> IResult<&str, Vec<T>>
+where
+    T: FromStr + num::Unsigned,
+{
+    list(parse_uint)(input)
+}
+
+/// Parses one or more signed integers separated by horizontal whitespace
+pub fn int_list<T>(input: &str) -> IResult<&str, Vec<T>>
+where
+    T: FromStr + num::Signed,
+{
+    list(parse_int)(input)
+}
+
+/// Parses a `label: value` pair, allowing any amount of horizontal
+/// whitespace after the colon (e.g. `Time:      7  15   30`)
+pub fn labelled<'a, L, V, FL, FV>(
+    label: FL,
+    value: FV,
+) -> impl FnMut(&'a str) -> IResult<&'a str, (L, V)>
+where
+    FL: Parser<&'a str, L, nom::error::Error<&'a str>>,
+    FV: Parser<&'a str, V, nom::error::Error<&'a str>>,
+{
+    separated_pair(label, pair(char(':'), space0), value)
+}
+
+/// Parses one or more lines separated by single line endings
+pub fn lines<'a, T, F>(line: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    F: Parser<&'a str, T, nom::error::Error<&'a str>>,
+{
+    separated_list1(line_ending, line)
+}
+
+/// Parses one or more blocks separated by a blank line
+pub fn blocks<'a, T, F>(block: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<T>>
+where
+    F: Parser<&'a str, T, nom::error::Error<&'a str>>,
+{
+    separated_list1(blank_line, block)
+}
+
+/// Parses the separator between two blocks (a line ending followed by an empty line)
+pub fn blank_line(input: &str) -> IResult<&str, &str> {
+    recognize(pair(line_ending, line_ending))(input)
+}
+
+/// Parses a rectangular or ragged grid where each line holds one or more cells
+pub fn grid<'a, T, F>(cell: F) -> impl FnMut(&'a str) -> IResult<&'a str, Vec<Vec<T>>>
+where
+    F: Parser<&'a str, T, nom::error::Error<&'a str>>,
+{
+    lines(many1(cell))
+}
+
+/// Parses a keyword followed by horizontal whitespace and an unsigned id
+/// (e.g. `Game 12` or `Card   3`)
+pub fn keyword_id<'a, T>(keyword: &'a str) -> impl FnMut(&'a str) -> IResult<&'a str, T>
+where
+    T: FromStr + num::Unsigned,
+{
+    preceded(pair(nom::bytes::complete::tag(keyword), space1), parse_uint)
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::{bytes::complete::tag, character::complete::anychar};
+
+    use super::*;
+
+    #[test]
+    fn uint_parses_any_width() {
+        assert_eq!(parse_uint::<u8>("255 rest"), Ok((" rest", 255)));
+        assert_eq!(
+            parse_uint::<u64>("18446744073709551615"),
+            Ok(("", u64::MAX))
+        );
+        assert_eq!(
+            parse_uint::<u128>("340282366920938463463374607431768211455"),
+            Ok(("", u128::MAX))
+        );
+    }
+
+    #[test]
+    fn uint_rejects_overflow_empty_and_signs() {
+        assert!(parse_uint::<u8>("256").is_err());
+        assert!(parse_uint::<u32>("").is_err());
+        assert!(parse_uint::<u32>("abc").is_err());
+        assert!(parse_uint::<u32>("-1").is_err());
+    }
+
+    #[test]
+    fn int_parses_signs() {
+        assert_eq!(parse_int::<i64>("-42,"), Ok((",", -42)));
+        assert_eq!(parse_int::<i64>("+42"), Ok(("", 42)));
+        assert_eq!(parse_int::<i8>("-128"), Ok(("", -128)));
+        assert!(parse_int::<i8>("128").is_err());
+        assert!(parse_int::<i32>("-").is_err());
+        assert!(parse_int::<i32>("").is_err());
+    }
+
+    #[test]
+    fn lists_allow_variable_spacing() {
+        assert_eq!(
+            uint_list::<u32>("7  15   30\n"),
+            Ok(("\n", vec![7, 15, 30]))
+        );
+        assert_eq!(int_list::<i32>("1 -2 3"), Ok(("", vec![1, -2, 3])));
+        assert_eq!(uint_list::<u32>("1 2 | 3"), Ok((" | 3", vec![1, 2])));
+        assert!(uint_list::<u32>("").is_err());
+    }
+
+    #[test]
+    fn labelled_lines() {
+        assert_eq!(
+            labelled(tag("Time"), uint_list::<u64>)("Time:      7  15   30"),
+            Ok(("", ("Time", vec![7, 15, 30])))
+        );
+        assert_eq!(
+            labelled(keyword_id::<u32>("Card"), uint_list::<u32>)("Card   3:  1 21"),
+            Ok(("", (3, vec![1, 21])))
+        );
+        assert!(labelled(tag("Time"), uint_list::<u64>)("Distance: 9").is_err());
+    }
+
+    #[test]
+    fn lines_and_blocks() {
+        assert_eq!(
+            lines(uint_list::<u8>)("1 2\r\n3\n4"),
+            Ok(("", vec![vec![1, 2], vec![3], vec![4]]))
+        );
+        assert_eq!(
+            blocks(lines(parse_uint::<u8>))("1\n2\n\n3\n\r\n4"),
+            Ok(("", vec![vec![1, 2], vec![3], vec![4]]))
+        );
+
+        // A trailing blank line is left unconsumed
+        assert_eq!(
+            blocks(lines(parse_uint::<u8>))("1\n\n"),
+            Ok(("\n\n", vec![vec![1]]))
+        );
+    }
+
+    #[test]
+    fn grids() {
+        assert_eq!(
+            grid(anychar_except_newline)("#.\n.#\n#"),
+            Ok(("", vec![vec!['#', '.'], vec!['.', '#'], vec!['#']]))
+        );
+        assert_eq!(
+            grid(one_of("01"))("01\n10\n\nrest"),
+            Ok(("\n\nrest", vec![vec!['0', '1'], vec!['1', '0']]))
+        );
+    }
+
+    fn anychar_except_newline(input: &str) -> IResult<&str, char> {
+        nom::combinator::verify(anychar, |c| *c != '\n' && *c != '\r')(input)
+    }
+}
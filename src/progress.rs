@@ -0,0 +1,281 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::runner::{PartReport, Status};
+
+/// How far along a part is, from the point of view of the repository
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartStatus {
+    /// There is no solution, or it still returns `None`
+    Unimplemented,
+    /// The solution does not produce the answer given in the example
+    ExamplesFailing,
+    /// The example passes, but the solution panics or returns nothing for
+    /// the real input
+    Failing,
+    /// The solution disagrees with the accepted answer for the real input
+    Incorrect,
+    /// The example passes, but no accepted answer has been recorded
+    Solved,
+    /// The solution reproduces the accepted answer for the real input
+    Verified,
+}
+
+impl PartStatus {
+    /// Combines the reports from running a part against its example and
+    /// against the real input
+    pub fn from_reports(example: Option<&PartReport>, actual: Option<&PartReport>) -> Self {
+        match example.map(|r| r.status) {
+            None | Some(Status::NoAnswer) => return Self::Unimplemented,
+            Some(Status::Error | Status::Incorrect) => return Self::ExamplesFailing,
+            Some(Status::Solved) => {}
+        }
+
+        // Without timings the input could not be loaded, so the part never ran
+        let ran = |r: &PartReport| r.parse_ns.is_some() || r.solve_ns.is_some();
+
+        match actual {
+            Some(r) if r.status == Status::Error && ran(r) => Self::Failing,
+            Some(r) if r.status == Status::NoAnswer => Self::Failing,
+            Some(r) if r.status == Status::Incorrect => Self::Incorrect,
+            Some(r) if r.status == Status::Solved && r.expected.is_some() => Self::Verified,
+            _ => Self::Solved,
+        }
+    }
+
+    /// How the status is shown in the README
+    pub fn emoji(self) -> &'static str {
+        match self {
+            PartStatus::Unimplemented => "",
+            PartStatus::ExamplesFailing | PartStatus::Failing | PartStatus::Incorrect => ":x:",
+            PartStatus::Solved => ":heavy_check_mark:",
+            PartStatus::Verified => ":star:",
+        }
+    }
+}
+
+impl fmt::Display for PartStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PartStatus::Unimplemented => "unimplemented",
+            PartStatus::ExamplesFailing => "examples failing",
+            PartStatus::Failing => "failing",
+            PartStatus::Incorrect => "incorrect",
+            PartStatus::Solved => "solved",
+            PartStatus::Verified => "verified",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DayStatus {
+    pub day: u32,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
+}
+
+impl DayStatus {
+    pub fn is_started(&self) -> bool {
+        self.part_1 != PartStatus::Unimplemented || self.part_2 != PartStatus::Unimplemented
+    }
+}
+
+/// Renders the README results table for every day up to the last one that
+/// has been started
+pub fn results_table(days: &[DayStatus]) -> String {
+    let last = days
+        .iter()
+        .rposition(DayStatus::is_started)
+        .map_or(0, |i| i + 1);
+
+    let rows: Vec<[String; 3]> = days[..last]
+        .iter()
+        .map(|d| {
+            [
+                format!("[Day {0}](https://adventofcode.com/2023/day/{0})", d.day),
+                d.part_1.emoji().to_string(),
+                d.part_2.emoji().to_string(),
+            ]
+        })
+        .collect();
+
+    let width = |column: usize, header: &str| {
+        rows.iter()
+            .map(|r| r[column].chars().count())
+            .chain([header.len(), 6])
+            .max()
+            .unwrap()
+    };
+    let widths = [width(0, "Day"), width(1, "Part 1"), width(2, "Part 2")];
+
+    let mut table = format!(
+        "| {:<w0$} | {:^w1$} | {:^w2$} |\n| {} | :{}: | :{}: |\n",
+        "Day",
+        "Part 1",
+        "Part 2",
+        "-".repeat(widths[0]),
+        "-".repeat(widths[1] - 2),
+        "-".repeat(widths[2] - 2),
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2],
+    );
+
+    for [day, part_1, part_2] in rows {
+        table += &format!(
+            "| {:<w0$} | {:^w1$} | {:^w2$} |\n",
+            day,
+            part_1,
+            part_2,
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+    }
+
+    table
+}
+
+/// Replaces the table in the `## Results` section of the README. Returns
+/// `None` if the README has no such section.
+pub fn sync_readme(readme: &str, table: &str) -> Option<String> {
+    let heading = "## Results\n";
+    let section = readme.find(heading)? + heading.len();
+
+    // The table is the first run of lines starting with `|` in the section
+    let mut start = None;
+    let mut end = readme.len();
+    let mut offset = section;
+
+    for line in readme[section..].split_inclusive('\n') {
+        match (start, line.starts_with('|')) {
+            (None, true) => start = Some(offset),
+            (Some(_), false) => {
+                end = offset;
+                break;
+            }
+            (None, false) if line.starts_with("## ") => return None,
+            _ => {}
+        }
+        offset += line.len();
+    }
+
+    let start = start?;
+    Some(format!("{}{}{}", &readme[..start], table, &readme[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(status: Status, expected: Option<&str>) -> PartReport {
+        PartReport {
+            day: 1,
+            part: 1,
+            status,
+            answer: Some("1".to_string()),
+            expected: expected.map(str::to_string),
+            load_ns: 0,
+            parse_ns: None,
+            solve_ns: Some(0),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn derives_status() {
+        let passing = report(Status::Solved, Some("1"));
+
+        assert_eq!(
+            PartStatus::from_reports(None, None),
+            PartStatus::Unimplemented
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&report(Status::NoAnswer, Some("1"))), None),
+            PartStatus::Unimplemented
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&report(Status::Incorrect, Some("2"))), Some(&passing)),
+            PartStatus::ExamplesFailing
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&report(Status::Error, Some("1")))),
+            PartStatus::Failing
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&report(Status::NoAnswer, None))),
+            PartStatus::Failing
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), None),
+            PartStatus::Solved
+        );
+
+        let missing_input = PartReport {
+            solve_ns: None,
+            error: Some("Input file not found".to_string()),
+            ..report(Status::Error, None)
+        };
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&missing_input)),
+            PartStatus::Solved
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&report(Status::Solved, None))),
+            PartStatus::Solved
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&report(Status::Incorrect, Some("2")))),
+            PartStatus::Incorrect
+        );
+        assert_eq!(
+            PartStatus::from_reports(Some(&passing), Some(&passing)),
+            PartStatus::Verified
+        );
+    }
+
+    fn days() -> Vec<DayStatus> {
+        let mut days: Vec<_> = (1..=25)
+            .map(|day| DayStatus {
+                day,
+                part_1: PartStatus::Unimplemented,
+                part_2: PartStatus::Unimplemented,
+            })
+            .collect();
+
+        days[0].part_1 = PartStatus::Verified;
+        days[0].part_2 = PartStatus::Verified;
+        days[1].part_1 = PartStatus::Solved;
+        days[2].part_1 = PartStatus::ExamplesFailing;
+        days
+    }
+
+    #[test]
+    fn renders_results_table() {
+        assert_eq!(
+            results_table(&days()),
+            "\
+| Day                                          |       Part 1       | Part 2 |
+| -------------------------------------------- | :----------------: | :----: |
+| [Day 1](https://adventofcode.com/2023/day/1) |       :star:       | :star: |
+| [Day 2](https://adventofcode.com/2023/day/2) | :heavy_check_mark: |        |
+| [Day 3](https://adventofcode.com/2023/day/3) |        :x:         |        |
+"
+        );
+    }
+
+    #[test]
+    fn syncs_readme() {
+        let readme = "# Title\n\n## Results\n\n| old |\n| --- |\n\n## Benchmarks\n\n| a |\n";
+        let table = "| new |\n";
+
+        assert_eq!(
+            sync_readme(readme, table).unwrap(),
+            "# Title\n\n## Results\n\n| new |\n\n## Benchmarks\n\n| a |\n"
+        );
+        assert_eq!(sync_readme("# Title\n", table), None);
+        assert_eq!(sync_readme("## Results\n\n## Other\n| a |\n", table), None);
+    }
+}
@@ -0,0 +1,643 @@
+use std::{
+    fmt::{Debug, Display},
+    panic::{self, AssertUnwindSafe},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    answers::Answers, input::load_example_file, load_input_file_with, parallel, Normalize, Part,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human readable lines such as `Part 1: Some(123) (in 24.2µs)`
+    #[default]
+    Text,
+    /// A single JSON array holding one record per part
+    Json,
+    /// One JSON record per line, so the output of several days can be concatenated
+    Ndjson,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            _ => Err(format!(
+                "Unknown output format `{}` (expected text, json or ndjson)",
+                s
+            )),
+        }
+    }
+}
+
+/// Command line options accepted by every solution binary
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunnerOptions {
+    pub format: OutputFormat,
+    /// Run each part against its example instead of the real input
+    pub examples: bool,
+    /// Size of rayon's thread pool (defaults to the number of cores)
+    pub threads: Option<usize>,
+    /// Use the sequential code paths instead of rayon
+    pub sequential: bool,
+    /// Print a breakdown of how each part reaches its answer
+    pub explain: bool,
+    /// Answer a day-specific question about the input instead of running the
+    /// parts (everything after `--query`)
+    pub query: Option<Vec<String>>,
+}
+
+impl RunnerOptions {
+    pub fn from_env() -> Result<Self, String> {
+        Self::parse(std::env::args().skip(1))
+    }
+
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut options = Self::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+                None => (arg, None),
+            };
+
+            let mut value = || {
+                inline_value
+                    .clone()
+                    .or_else(|| args.next())
+                    .ok_or_else(|| format!("Missing value for `{}`", flag))
+            };
+
+            match flag.as_str() {
+                "--format" => options.format = value()?.parse()?,
+                "--examples" => options.examples = true,
+                "--sequential" => options.sequential = true,
+                "--explain" => options.explain = true,
+                "--query" => options.query = Some(args.by_ref().collect()),
+                "--threads" => {
+                    let threads = value()?;
+                    options.threads = match threads.parse() {
+                        Ok(0) | Err(_) => {
+                            return Err(format!("Invalid number of threads `{}`", threads))
+                        }
+                        Ok(threads) => Some(threads),
+                    };
+                }
+                _ => return Err(format!("Unknown argument `{}`", flag)),
+            }
+        }
+
+        if options.explain && options.format != OutputFormat::Text {
+            return Err("`--explain` can only be used with the text format".to_string());
+        }
+        if options.query.is_some() && options.format != OutputFormat::Text {
+            return Err("`--query` can only be used with the text format".to_string());
+        }
+
+        Ok(options)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Status {
+    /// The part produced an answer (matching the expected one, if known)
+    Solved,
+    /// The part produced an answer that differs from the expected one
+    Incorrect,
+    /// The part ran but returned `None`
+    NoAnswer,
+    /// The input could not be loaded or the part panicked
+    Error,
+}
+
+/// The outcome of running one part of a day
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PartReport {
+    pub day: u32,
+    pub part: u8,
+    pub status: Status,
+    /// The answer formatted with `Display`, kept as a string so large
+    /// numbers survive JSON consumers that only have doubles
+    pub answer: Option<String>,
+    /// The answer from the example file, or the accepted answer recorded in
+    /// `data/answers.json`
+    pub expected: Option<String>,
+    /// Time spent reading the input file, shared by both parts
+    pub load_ns: u64,
+    /// Time taken by the day's parse hook, if it has one (see [`Hooks::parse`])
+    pub parse_ns: Option<u64>,
+    /// Time spent inside the part function (absent if it never ran)
+    pub solve_ns: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Describes how a part of a day arrives at its answer for the given input
+pub type Explain = fn(&str, Part) -> String;
+
+/// Answers a day-specific question about the input, given the arguments
+/// following `--query`
+pub type Query = fn(&str, &[String]) -> Result<String, String>;
+
+/// Parses the input, returning the number of records or why it is invalid
+pub type Parse = fn(&str) -> Result<usize, String>;
+
+/// Optional features that a day can plug into the runner
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hooks {
+    pub explain: Option<Explain>,
+    pub query: Option<Query>,
+    /// Run on its own before each part, to report `parse_ns` and to reject
+    /// invalid inputs with a clear error. The parts still parse the input
+    /// themselves, so this is a separate measurement that overlaps with
+    /// `solve_ns` rather than a share of it.
+    pub parse: Option<Parse>,
+}
+
+/// Runs both parts of a day against its input and prints the results in the
+/// format requested on the command line. Exits with a non-zero status if
+/// anything failed.
+pub fn run<A, B>(
+    day: u32,
+    normalize: Normalize,
+    part_1: fn(&str) -> Option<A>,
+    part_2: fn(&str) -> Option<B>,
+) where
+    A: Display + Debug,
+    B: Display + Debug,
+{
+    run_with_hooks(day, normalize, part_1, part_2, Hooks::default());
+}
+
+/// Like [`run`], for days that support `--explain`, `--query` or parse timings
+pub fn run_with_hooks<A, B>(
+    day: u32,
+    normalize: Normalize,
+    part_1: fn(&str) -> Option<A>,
+    part_2: fn(&str) -> Option<B>,
+    hooks: Hooks,
+) where
+    A: Display + Debug,
+    B: Display + Debug,
+{
+    let options = RunnerOptions::from_env().unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!(
+            "Usage: [--format text|json|ndjson] [--examples] [--explain] [--threads N | --sequential] [--query ...]"
+        );
+        std::process::exit(2);
+    });
+
+    if options.explain && hooks.explain.is_none() {
+        eprintln!("Day {:02} cannot explain its answers", day);
+        std::process::exit(2);
+    }
+
+    if let Some(args) = &options.query {
+        let Some(query) = hooks.query else {
+            eprintln!("Day {:02} does not answer queries", day);
+            std::process::exit(2);
+        };
+
+        run_query(day, normalize, options.examples, query, args);
+        return;
+    }
+
+    let explain = hooks.explain.filter(|_| options.explain);
+    let print_explanation = |part: Part, input: Result<&str, &str>| {
+        if let (Some(explain), Ok(input)) = (explain, input) {
+            println!("Part {} breakdown:\n{}\n", part as u8, explain(input, part));
+        }
+    };
+
+    if options.sequential {
+        parallel::set_sequential(true);
+    }
+    if let Some(threads) = options.threads {
+        parallel::set_threads(threads).unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            std::process::exit(2);
+        });
+    }
+
+    let reports = if options.examples {
+        [Part::One, Part::Two].map(|part| {
+            let start_time = Instant::now();
+            let example = load_example_file(day, part, normalize).map_err(|e| e.to_string());
+            let load_time = start_time.elapsed();
+
+            let (expected, input) = match &example {
+                Ok((expected, input)) => (Some(expected.as_str()), Ok(input.as_str())),
+                Err(e) => (None, Err(e.as_str())),
+            };
+            print_explanation(part, input);
+
+            match part {
+                Part::One => run_part(day, part, input, expected, load_time, hooks.parse, part_1),
+                Part::Two => run_part(day, part, input, expected, load_time, hooks.parse, part_2),
+            }
+        })
+    } else {
+        let answers = Answers::load().unwrap_or_else(|e| {
+            eprintln!("Warning: could not read the recorded answers: {}", e);
+            Answers::default()
+        });
+
+        let start_time = Instant::now();
+        let input = load_input_file_with(day, normalize).map_err(|e| e.to_string());
+        let load_time = start_time.elapsed();
+
+        let input = input.as_deref().map_err(String::as_str);
+        print_explanation(Part::One, input);
+        print_explanation(Part::Two, input);
+
+        [
+            run_part(
+                day,
+                Part::One,
+                input,
+                answers.get(day, Part::One),
+                load_time,
+                hooks.parse,
+                part_1,
+            ),
+            run_part(
+                day,
+                Part::Two,
+                input,
+                answers.get(day, Part::Two),
+                load_time,
+                hooks.parse,
+                part_2,
+            ),
+        ]
+    };
+
+    match options.format {
+        OutputFormat::Text => {
+            for (report, debug) in &reports {
+                match report.status {
+                    Status::Error => println!(
+                        "Part {}: ERROR {}",
+                        report.part,
+                        report.error.as_deref().unwrap_or_default()
+                    ),
+                    Status::Incorrect => println!(
+                        "Part {}: {} (in {:.2?}) INCORRECT, expected {}",
+                        report.part,
+                        debug,
+                        Duration::from_nanos(report.solve_ns.unwrap_or_default()),
+                        report.expected.as_deref().unwrap_or_default()
+                    ),
+                    _ => println!(
+                        "Part {}: {} (in {:.2?})",
+                        report.part,
+                        debug,
+                        Duration::from_nanos(report.solve_ns.unwrap_or_default())
+                    ),
+                }
+            }
+        }
+        OutputFormat::Json => {
+            let reports: Vec<_> = reports.iter().map(|(r, _)| r).collect();
+            println!("{}", serde_json::to_string_pretty(&reports).unwrap());
+        }
+        OutputFormat::Ndjson => {
+            for (report, _) in &reports {
+                println!("{}", serde_json::to_string(report).unwrap());
+            }
+        }
+    }
+
+    if reports
+        .iter()
+        .any(|(r, _)| matches!(r.status, Status::Error | Status::Incorrect))
+    {
+        std::process::exit(1);
+    }
+}
+
+/// Answers a query about the input, or the part 1 example with `--examples`.
+/// Exits with a non-zero status if the query fails.
+fn run_query(day: u32, normalize: Normalize, examples: bool, query: Query, args: &[String]) {
+    let input = if examples {
+        load_example_file(day, Part::One, normalize).map(|(_, input)| input)
+    } else {
+        load_input_file_with(day, normalize)
+    };
+
+    let result = input
+        .map_err(|e| e.to_string())
+        .and_then(|input| query(&input, args));
+
+    match result {
+        Ok(answer) => println!("{}", answer),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs a single part, returning its report along with the `Debug` form of
+/// the result for the text output. If the day has a parser, it is timed first
+/// and the part is not run when the parser rejects the input.
+fn run_part<T>(
+    day: u32,
+    part: Part,
+    input: Result<&str, &str>,
+    expected: Option<&str>,
+    load_time: Duration,
+    parse: Option<Parse>,
+    solve: fn(&str) -> Option<T>,
+) -> (PartReport, String)
+where
+    T: Display + Debug,
+{
+    let mut report = PartReport {
+        day,
+        part: part as u8,
+        status: Status::Error,
+        answer: None,
+        expected: expected.map(str::to_string),
+        load_ns: load_time.as_nanos() as u64,
+        parse_ns: None,
+        solve_ns: None,
+        error: None,
+    };
+
+    let input = match input {
+        Ok(input) => input,
+        Err(error) => {
+            report.error = Some(error.to_string());
+            return (report, String::new());
+        }
+    };
+
+    if let Some(parse) = parse {
+        let start_time = Instant::now();
+        let parsed = panic::catch_unwind(|| parse(input));
+        report.parse_ns = Some(start_time.elapsed().as_nanos() as u64);
+
+        let error = match parsed {
+            Ok(Ok(_)) => None,
+            Ok(Err(error)) => Some(error),
+            Err(payload) => Some(panic_message(payload.as_ref())),
+        };
+
+        if let Some(error) = error {
+            report.error = Some(format!("Invalid input: {}", error));
+            return (report, String::new());
+        }
+    }
+
+    let start_time = Instant::now();
+    let result = panic::catch_unwind(AssertUnwindSafe(|| solve(input)));
+    report.solve_ns = Some(start_time.elapsed().as_nanos() as u64);
+
+    match result {
+        Ok(answer) => {
+            let debug = format!("{:?}", answer);
+
+            report.answer = answer.map(|a| a.to_string());
+            report.status = match (&report.answer, expected) {
+                (None, _) => Status::NoAnswer,
+                (Some(answer), Some(expected)) if answer != expected => Status::Incorrect,
+                (Some(_), _) => Status::Solved,
+            };
+
+            (report, debug)
+        }
+        Err(payload) => {
+            report.error = Some(panic_message(payload.as_ref()));
+            (report, String::new())
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panicked".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_format_flag() {
+        assert_eq!(
+            RunnerOptions::parse(args(&[])).unwrap().format,
+            OutputFormat::Text
+        );
+        assert_eq!(
+            RunnerOptions::parse(args(&["--format", "json"]))
+                .unwrap()
+                .format,
+            OutputFormat::Json
+        );
+        assert_eq!(
+            RunnerOptions::parse(args(&["--format=ndjson"]))
+                .unwrap()
+                .format,
+            OutputFormat::Ndjson
+        );
+        assert!(RunnerOptions::parse(args(&["--format", "xml"])).is_err());
+        assert!(RunnerOptions::parse(args(&["--format"])).is_err());
+        assert!(RunnerOptions::parse(args(&["--verbose"])).is_err());
+
+        let options = RunnerOptions::parse(args(&["--examples", "--format=json"])).unwrap();
+        assert!(options.examples);
+        assert_eq!(options.format, OutputFormat::Json);
+    }
+
+    #[test]
+    fn parses_parallelism_flags() {
+        let options = RunnerOptions::parse(args(&["--threads", "4"])).unwrap();
+        assert_eq!(options.threads, Some(4));
+        assert!(!options.sequential);
+
+        assert!(
+            RunnerOptions::parse(args(&["--sequential"]))
+                .unwrap()
+                .sequential
+        );
+        assert!(RunnerOptions::parse(args(&["--threads=0"])).is_err());
+        assert!(RunnerOptions::parse(args(&["--threads", "many"])).is_err());
+    }
+
+    #[test]
+    fn explain_requires_text_output() {
+        assert!(RunnerOptions::parse(args(&["--explain"])).unwrap().explain);
+        assert!(RunnerOptions::parse(args(&["--explain", "--format=json"])).is_err());
+    }
+
+    #[test]
+    fn query_takes_the_remaining_arguments() {
+        let options = RunnerOptions::parse(args(&["--examples", "--query", "tightest", "3"]));
+        let options = options.unwrap();
+
+        assert!(options.examples);
+        assert_eq!(options.query, Some(args(&["tightest", "3"])));
+        assert_eq!(
+            RunnerOptions::parse(args(&["--query"])).unwrap().query,
+            Some(vec![])
+        );
+        assert_eq!(RunnerOptions::parse(args(&[])).unwrap().query, None);
+    }
+
+    #[test]
+    fn reports_answers_and_failures() {
+        let (report, debug) = run_part(
+            1,
+            Part::One,
+            Ok("1 2"),
+            None,
+            Duration::ZERO,
+            None,
+            |input| Some(input.split(' ').count() as u128 * 10_000_000_000_000_000_000),
+        );
+        assert_eq!(report.status, Status::Solved);
+        assert_eq!(report.answer.as_deref(), Some("20000000000000000000"));
+        assert_eq!(debug, "Some(20000000000000000000)");
+
+        let (report, _) =
+            run_part::<u32>(1, Part::Two, Ok(""), None, Duration::ZERO, None, |_| None);
+        assert_eq!(report.status, Status::NoAnswer);
+        assert_eq!(report.answer, None);
+
+        let (report, _) = run_part::<u32>(1, Part::Two, Ok(""), None, Duration::ZERO, None, |_| {
+            panic!("bad input")
+        });
+        assert_eq!(report.status, Status::Error);
+        assert_eq!(report.error.as_deref(), Some("bad input"));
+
+        let (report, _) = run_part::<u32>(
+            3,
+            Part::One,
+            Err("missing"),
+            None,
+            Duration::ZERO,
+            None,
+            |_| Some(1),
+        );
+        assert_eq!(report.status, Status::Error);
+        assert_eq!(report.solve_ns, None);
+    }
+
+    #[test]
+    fn times_parsing_separately() {
+        let parse: Parse = |input| match input {
+            "" => Err("no records".to_string()),
+            _ => Ok(input.lines().count()),
+        };
+
+        let (report, _) = run_part(
+            1,
+            Part::One,
+            Ok("1\n2"),
+            None,
+            Duration::ZERO,
+            Some(parse),
+            |_| Some(3),
+        );
+        assert_eq!(report.status, Status::Solved);
+        assert!(report.parse_ns.is_some() && report.solve_ns.is_some());
+
+        let (report, _) = run_part(
+            1,
+            Part::One,
+            Ok(""),
+            None,
+            Duration::ZERO,
+            Some(parse),
+            |_| Some(3),
+        );
+        assert_eq!(report.status, Status::Error);
+        assert_eq!(report.error.as_deref(), Some("Invalid input: no records"));
+        assert!(report.parse_ns.is_some());
+        assert_eq!(report.solve_ns, None);
+
+        let (report, _) = run_part(1, Part::One, Ok("1"), None, Duration::ZERO, None, |_| {
+            Some(3)
+        });
+        assert_eq!(report.parse_ns, None);
+    }
+
+    #[test]
+    fn compares_with_expected_answers() {
+        let solve = |input: &str| Some(input.len());
+
+        let (report, _) = run_part(
+            1,
+            Part::One,
+            Ok("abc"),
+            Some("3"),
+            Duration::ZERO,
+            None,
+            solve,
+        );
+        assert_eq!(report.status, Status::Solved);
+        assert_eq!(report.expected.as_deref(), Some("3"));
+
+        let (report, _) = run_part(
+            1,
+            Part::One,
+            Ok("abcd"),
+            Some("3"),
+            Duration::ZERO,
+            None,
+            solve,
+        );
+        assert_eq!(report.status, Status::Incorrect);
+        assert_eq!(report.answer.as_deref(), Some("4"));
+
+        let (report, _) = run_part::<u32>(
+            1,
+            Part::One,
+            Ok(""),
+            Some("3"),
+            Duration::ZERO,
+            None,
+            |_| None,
+        );
+        assert_eq!(report.status, Status::NoAnswer);
+    }
+
+    #[test]
+    fn serializes_reports() {
+        let report = PartReport {
+            day: 5,
+            part: 2,
+            status: Status::NoAnswer,
+            answer: None,
+            expected: None,
+            load_ns: 10,
+            parse_ns: Some(5),
+            solve_ns: Some(20),
+            error: None,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"day":5,"part":2,"status":"no_answer","answer":null,"expected":null,"load_ns":10,"parse_ns":5,"solve_ns":20,"error":null}"#
+        );
+    }
+}
@@ -0,0 +1,22 @@
+//! Helpers shared by the tests
+
+/// A seeded xorshift generator, so generated test inputs are the same on
+/// every run without depending on a random number crate
+pub struct Rng(u64);
+
+impl Rng {
+    /// Panics if the seed is zero, which xorshift never leaves
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "The seed must not be zero");
+        Self(seed)
+    }
+
+    /// A number in `0..bound`
+    pub fn below(&mut self, bound: usize) -> usize {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        (self.0 % bound as u64) as usize
+    }
+}
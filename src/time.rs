@@ -0,0 +1,104 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seconds since the Unix epoch
+pub fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs() as i64)
+}
+
+/// Converts a UTC calendar date and time to seconds since the Unix epoch
+pub fn unix_from_utc(year: i64, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> i64 {
+    days_from_civil(year, month, day) * 86_400 + (hour * 3_600 + min * 60 + sec) as i64
+}
+
+/// Formats seconds since the Unix epoch as `YYYY-MM-DD HH:MM:SS UTC`
+pub fn format_utc(timestamp: i64) -> String {
+    let days = timestamp.div_euclid(86_400);
+    let seconds = timestamp.rem_euclid(86_400);
+    let (year, month, day) = civil_from_days(days);
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02} UTC",
+        year,
+        month,
+        day,
+        seconds / 3_600,
+        seconds % 3_600 / 60,
+        seconds % 60
+    )
+}
+
+/// Formats a number of seconds as e.g. `1h 02m 03s`
+pub fn format_duration(seconds: i64) -> String {
+    let sign = if seconds < 0 { "-" } else { "" };
+    let seconds = seconds.unsigned_abs();
+    let (h, m, s) = (seconds / 3_600, seconds % 3_600 / 60, seconds % 60);
+
+    match (h, m) {
+        (0, 0) => format!("{}{}s", sign, s),
+        (0, _) => format!("{}{}m {:02}s", sign, m, s),
+        _ => format!("{}{}h {:02}m {:02}s", sign, h, m, s),
+    }
+}
+
+// Howard Hinnant's algorithms for converting between days since the epoch
+// and proleptic Gregorian dates (http://howardhinnant.github.io/date_algorithms.html)
+
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    era * 146_097 + day_of_era - 719_468
+}
+
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_dates() {
+        assert_eq!(unix_from_utc(1970, 1, 1, 0, 0, 0), 0);
+        assert_eq!(unix_from_utc(2023, 12, 1, 5, 0, 0), 1_701_406_800);
+        assert_eq!(unix_from_utc(2024, 2, 29, 23, 59, 59), 1_709_251_199);
+
+        assert_eq!(format_utc(0), "1970-01-01 00:00:00 UTC");
+        assert_eq!(format_utc(1_701_406_800), "2023-12-01 05:00:00 UTC");
+        assert_eq!(format_utc(1_709_251_199), "2024-02-29 23:59:59 UTC");
+        assert_eq!(format_utc(-1), "1969-12-31 23:59:59 UTC");
+
+        for days in -800_000..800_000 {
+            let (y, m, d) = civil_from_days(days);
+            assert_eq!(days_from_civil(y, m, d), days);
+        }
+    }
+
+    #[test]
+    fn formats_durations() {
+        assert_eq!(format_duration(5), "5s");
+        assert_eq!(format_duration(65), "1m 05s");
+        assert_eq!(format_duration(3_723), "1h 02m 03s");
+        assert_eq!(format_duration(90_000), "25h 00m 00s");
+        assert_eq!(format_duration(-65), "-1m 05s");
+    }
+}